@@ -0,0 +1,144 @@
+//! End-to-end lookup timings over a synthetic crate with 50,000 pages.
+//!
+//! Run with `cargo bench`. Set `PAGES` to change the size of the crate.
+
+use {
+  std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+  },
+  tempfile::TempDir,
+};
+
+const MODULE_SIZE: usize = 1000;
+
+const RUNS: usize = 5;
+
+fn function_page(module: usize, function: usize) -> String {
+  format!(
+    r##"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8"><title>function_{function} in synthetic::module_{module} - Rust</title></head><body><main><div class="main-heading"><h1>Function <span class="fn">function_{function}</span></h1><span class="sub-heading"><a class="src" href="../../src/synthetic/module_{module}.rs.html#{function}">Source</a></span></div><pre class="rust item-decl"><code>pub fn function_{function}(value: <a class="primitive" href="https://doc.rust-lang.org/std/primitive.u64.html">u64</a>) -&gt; <a class="enum" href="https://doc.rust-lang.org/std/option/enum.Option.html">Option</a>&lt;<a class="struct" href="struct.Record.html">Record</a>&gt;</code></pre><details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>Looks up record {function} of module {module}, returning <a href="struct.Record.html"><code>Record</code></a> when present.</p><h2 id="examples"><a class="doc-anchor" href="#examples">§</a>Examples</h2><div class="example-wrap"><pre class="rust rust-example-rendered"><code><span class="kw">let </span>record = synthetic::module_{module}::function_{function}(<span class="number">1</span>);
+<span class="macro">assert!</span>(record.is_some());</code></pre></div><ul><li>Returns <code>None</code> for unknown values.</li><li>Never panics.</li></ul></div></details></main></body></html>"##
+  )
+}
+
+fn generate(root: &Path, pages: usize) {
+  let crate_path = root.join("target/doc/synthetic");
+
+  for module in 0..pages.div_ceil(MODULE_SIZE) {
+    let module_path = crate_path.join(format!("module_{module}"));
+
+    fs::create_dir_all(&module_path).unwrap();
+
+    fs::write(
+      module_path.join("index.html"),
+      format!(
+        r#"<!DOCTYPE html><html><body><pre class="rust item-decl"><code>mod module_{module}</code></pre></body></html>"#
+      ),
+    )
+    .unwrap();
+
+    for function in 0..MODULE_SIZE.min(pages - module * MODULE_SIZE) {
+      fs::write(
+        module_path.join(format!("fn.function_{function}.html")),
+        function_page(module, function),
+      )
+      .unwrap();
+    }
+  }
+}
+
+fn lookup(root: &Path, arguments: &[&str]) -> Duration {
+  let start = Instant::now();
+
+  let output = Command::new(env!("CARGO_BIN_EXE_crates-mcp"))
+    .current_dir(root)
+    .args(arguments)
+    .output()
+    .unwrap();
+
+  let elapsed = start.elapsed();
+
+  assert!(
+    output.status.success(),
+    "lookup failed: {}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  elapsed
+}
+
+fn bench(root: &Path, name: &str, arguments: &[&str]) {
+  let mut timings = (0..RUNS)
+    .map(|_| lookup(root, arguments))
+    .collect::<Vec<_>>();
+
+  timings.sort();
+
+  println!(
+    "{name:<32} median {:>8.1?}  min {:>8.1?}  max {:>8.1?}",
+    timings[RUNS / 2],
+    timings[0],
+    timings[RUNS - 1]
+  );
+}
+
+fn main() {
+  let pages = env::var("PAGES")
+    .ok()
+    .and_then(|pages| pages.parse().ok())
+    .unwrap_or(50_000);
+
+  let temp_dir = TempDir::new().unwrap();
+
+  let start = Instant::now();
+
+  generate(temp_dir.path(), pages);
+
+  println!("generated {pages} pages in {:.1?}", start.elapsed());
+
+  let root = temp_dir.path();
+
+  bench(
+    root,
+    "first page",
+    &["--no-cache", "lookup", "-n", "synthetic", "--limit", "50"],
+  );
+
+  bench(
+    root,
+    "query, early exit",
+    &[
+      "--no-cache",
+      "lookup",
+      "-n",
+      "synthetic",
+      "-q",
+      "record 5",
+      "--limit",
+      "10",
+    ],
+  );
+
+  bench(
+    root,
+    "query, full parse",
+    &[
+      "--no-cache",
+      "lookup",
+      "-n",
+      "synthetic",
+      "-q",
+      "no such item",
+    ],
+  );
+
+  lookup(root, &["lookup", "-n", "synthetic", "-q", "warm the cache"]);
+
+  bench(
+    root,
+    "query, cached",
+    &["lookup", "-n", "synthetic", "-q", "no such item"],
+  );
+}
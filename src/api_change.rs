@@ -0,0 +1,70 @@
+use super::*;
+
+/// One difference between two versions of a crate's API: an item, or a
+/// field, variant, method or impl of one, that was added, removed or changed,
+/// along with its declaration on either side.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ApiChange {
+  pub path: String,
+  pub item_type: String,
+  pub change: ChangeKind,
+  pub impact: SemverImpact,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub old: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub new: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ChangeKind {
+  Added,
+  Removed,
+  Changed,
+}
+
+impl ApiChange {
+  pub fn added(
+    path: &str,
+    item_type: &str,
+    impact: SemverImpact,
+    new: Option<&str>,
+  ) -> Self {
+    Self {
+      path: path.to_string(),
+      item_type: item_type.to_string(),
+      change: ChangeKind::Added,
+      impact,
+      old: None,
+      new: new.map(str::to_string),
+    }
+  }
+
+  /// Removing anything public can break code that uses it.
+  pub fn removed(path: &str, item_type: &str, old: Option<&str>) -> Self {
+    Self {
+      path: path.to_string(),
+      item_type: item_type.to_string(),
+      change: ChangeKind::Removed,
+      impact: SemverImpact::Major,
+      old: old.map(str::to_string),
+      new: None,
+    }
+  }
+
+  pub fn changed(
+    path: &str,
+    item_type: &str,
+    impact: SemverImpact,
+    old: Option<&str>,
+    new: Option<&str>,
+  ) -> Self {
+    Self {
+      path: path.to_string(),
+      item_type: item_type.to_string(),
+      change: ChangeKind::Changed,
+      impact,
+      old: old.map(str::to_string),
+      new: new.map(str::to_string),
+    }
+  }
+}
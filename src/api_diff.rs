@@ -0,0 +1,567 @@
+use super::*;
+
+/// Compare crates between two sides, each either a documentation directory
+/// or the name of a snapshot saved in `snapshots`, with the new side
+/// defaulting to `path` itself. Without a crate name, every crate found on
+/// both sides is compared.
+pub fn diff_docs(
+  request: &DiffDocsRequest,
+  path: &str,
+  snapshots: &Path,
+  cache: &Cache,
+) -> Result<Vec<CrateDiff>> {
+  let new = request.new.as_deref().unwrap_or(path);
+
+  let names = match &request.name {
+    Some(name) => vec![name.clone()],
+    None => {
+      let old = side_crates(&request.old, snapshots)?;
+
+      side_crates(new, snapshots)?
+        .into_iter()
+        .filter(|name| old.contains(name))
+        .collect()
+    }
+  };
+
+  names
+    .iter()
+    .map(|name| {
+      Ok(diff_crate(
+        &side_crate(&request.old, snapshots, name, cache)?,
+        &side_crate(new, snapshots, name, cache)?,
+      ))
+    })
+    .collect()
+}
+
+/// Save the items of the given crates, or of every crate under `path`, to a
+/// named snapshot in `snapshots`, adding to or replacing what it already
+/// holds. Returns the crates saved.
+pub fn save_snapshot(
+  request: &SaveSnapshotRequest,
+  path: &str,
+  snapshots: &Path,
+  cache: &Cache,
+) -> Result<Vec<String>> {
+  let snapshot = Snapshot::new(snapshots, &request.name)?;
+
+  let crates = match &request.crates {
+    Some(crates) => crates.clone(),
+    None => list_crates(path)?,
+  };
+
+  for name in &crates {
+    snapshot.save(&crate_api(name, path, cache)?)?;
+  }
+
+  Ok(crates)
+}
+
+fn side_crates(side: &str, snapshots: &Path) -> Result<Vec<String>> {
+  if Path::new(side).is_dir() {
+    list_crates(side)
+  } else {
+    snapshot(side, snapshots)?.crates()
+  }
+}
+
+fn side_crate(
+  side: &str,
+  snapshots: &Path,
+  name: &str,
+  cache: &Cache,
+) -> Result<Documentation> {
+  if Path::new(side).is_dir() {
+    crate_api(name, side, &cache.for_path(side))
+  } else {
+    snapshot(side, snapshots)?.load(name)
+  }
+}
+
+fn snapshot(name: &str, snapshots: &Path) -> Result<Snapshot> {
+  Snapshot::new(snapshots, name)
+    .ok()
+    .filter(Snapshot::exists)
+    .ok_or_else(|| {
+      anyhow!("no documentation directory or snapshot named '{name}'").into()
+    })
+}
+
+fn diff_crate(old: &Documentation, new: &Documentation) -> CrateDiff {
+  fn index(documentation: &Documentation) -> BTreeMap<&str, &Item> {
+    documentation
+      .items
+      .iter()
+      .map(|item| (item.path(), item))
+      .collect()
+  }
+
+  // Items that moved but are still reachable at their old path, such as
+  // through a re-export, haven't been removed.
+  fn aliases(documentation: &Documentation) -> HashSet<&str> {
+    documentation
+      .items
+      .iter()
+      .flat_map(Item::aliases)
+      .map(String::as_str)
+      .collect()
+  }
+
+  let (old_items, new_items) = (index(old), index(new));
+
+  let (old_aliases, new_aliases) = (aliases(old), aliases(new));
+
+  let mut changes = Vec::new();
+
+  for (path, item) in &old_items {
+    match new_items.get(path) {
+      Some(new_item) => diff_item(item, new_item, &mut changes),
+      None if !new_aliases.contains(path) => changes.push(ApiChange::removed(
+        path,
+        item.kind().name(),
+        item.signature(),
+      )),
+      None => {}
+    }
+  }
+
+  for (path, item) in &new_items {
+    if !old_items.contains_key(path) && !old_aliases.contains(path) {
+      changes.push(ApiChange::added(
+        path,
+        item.kind().name(),
+        SemverImpact::Minor,
+        item.signature(),
+      ));
+    }
+  }
+
+  changes.sort_by(|a, b| {
+    a.path
+      .cmp(&b.path)
+      .then_with(|| a.item_type.cmp(&b.item_type))
+  });
+
+  CrateDiff {
+    name: new.name.clone(),
+    old_version: old.version.clone(),
+    new_version: new.version.clone(),
+    impact: changes
+      .iter()
+      .map(|change| change.impact)
+      .max()
+      .unwrap_or_default(),
+    changes,
+  }
+}
+
+fn diff_item(old: &Item, new: &Item, changes: &mut Vec<ApiChange>) {
+  let path = new.path();
+
+  if old.kind() != new.kind() {
+    changes.push(ApiChange::changed(
+      path,
+      new.kind().name(),
+      SemverImpact::Major,
+      Some(old.signature().unwrap_or(old.kind().name())),
+      Some(new.signature().unwrap_or(new.kind().name())),
+    ));
+
+    return;
+  }
+
+  if declaration(old) != declaration(new) {
+    changes.push(ApiChange::changed(
+      path,
+      new.kind().name(),
+      SemverImpact::Major,
+      old.signature(),
+      new.signature(),
+    ));
+  }
+
+  match (old, new) {
+    (
+      Item::Struct {
+        signature,
+        fields: old_fields,
+        ..
+      },
+      Item::Struct {
+        fields: new_fields, ..
+      },
+    )
+    | (
+      Item::Union {
+        signature,
+        fields: old_fields,
+        ..
+      },
+      Item::Union {
+        fields: new_fields, ..
+      },
+    ) => {
+      // Code outside the crate can only build the old type with a literal if
+      // all of its fields were public, in which case a new field breaks it.
+      let extensible = non_exhaustive(signature)
+        || signature.contains("private field")
+        || old_fields.iter().any(|field| field.visibility != "pub");
+
+      diff_fields(path, old_fields, new_fields, extensible, changes);
+    }
+    (
+      Item::Enum {
+        signature,
+        variants: old_variants,
+        ..
+      },
+      Item::Enum {
+        variants: new_variants,
+        ..
+      },
+    ) => diff_variants(
+      path,
+      old_variants,
+      new_variants,
+      non_exhaustive(signature),
+      changes,
+    ),
+    (
+      Item::Trait {
+        required_methods: old_required,
+        provided_methods: old_provided,
+        ..
+      },
+      Item::Trait {
+        required_methods: new_required,
+        provided_methods: new_provided,
+        ..
+      },
+    ) => diff_trait_methods(
+      (old_required, old_provided),
+      (new_required, new_provided),
+      changes,
+    ),
+    _ => {}
+  }
+
+  diff_implementations(
+    path,
+    old.implementations(),
+    new.implementations(),
+    changes,
+  );
+}
+
+fn diff_fields(
+  path: &str,
+  old: &[Field],
+  new: &[Field],
+  extensible: bool,
+  changes: &mut Vec<ApiChange>,
+) {
+  let public = |fields: &[Field]| {
+    fields
+      .iter()
+      .filter(|field| field.visibility == "pub")
+      .map(|field| {
+        (field.name.clone(), format!("{}: {}", field.name, field.ty))
+      })
+      .collect::<Vec<_>>()
+  };
+
+  let impact = if extensible {
+    SemverImpact::Minor
+  } else {
+    SemverImpact::Major
+  };
+
+  diff_members(path, "field", public(old), public(new), impact, changes);
+}
+
+fn diff_variants(
+  path: &str,
+  old: &[Variant],
+  new: &[Variant],
+  non_exhaustive: bool,
+  changes: &mut Vec<ApiChange>,
+) {
+  let declarations = |variants: &[Variant]| {
+    variants
+      .iter()
+      .map(|variant| (variant.name.clone(), variant_declaration(variant)))
+      .collect::<Vec<_>>()
+  };
+
+  // Exhaustive matches on the old enum stop compiling when a variant is
+  // added, unless it was `#[non_exhaustive]`.
+  let impact = if non_exhaustive {
+    SemverImpact::Minor
+  } else {
+    SemverImpact::Major
+  };
+
+  diff_members(
+    path,
+    "variant",
+    declarations(old),
+    declarations(new),
+    impact,
+    changes,
+  );
+}
+
+/// Required methods break implementors when added, and provided ones don't.
+/// A method's declaration is shown with a `{ ... }` body when it has a
+/// default, so a required method becoming provided, or the reverse, shows up
+/// as a change.
+fn diff_trait_methods(
+  old: (&[Item], &[Item]),
+  new: (&[Item], &[Item]),
+  changes: &mut Vec<ApiChange>,
+) {
+  let declarations = |(required, provided): (&[Item], &[Item])| {
+    required
+      .iter()
+      .map(|method| (method, false))
+      .chain(provided.iter().map(|method| (method, true)))
+      .map(|(method, provided)| {
+        let signature = normalize(method.signature().unwrap_or_default());
+
+        (
+          method.path().to_string(),
+          (
+            if provided {
+              format!("{signature} {{ ... }}")
+            } else {
+              signature
+            },
+            provided,
+          ),
+        )
+      })
+      .collect::<BTreeMap<_, _>>()
+  };
+
+  let (old, new) = (declarations(old), declarations(new));
+
+  for (path, (declaration, _)) in &old {
+    match new.get(path) {
+      None => {
+        changes.push(ApiChange::removed(path, "method", Some(declaration)));
+      }
+      Some((new_declaration, provided)) if new_declaration != declaration => {
+        let unchanged_signature = declaration
+          .strip_suffix(" { ... }")
+          .is_some_and(|signature| signature == new_declaration)
+          || new_declaration
+            .strip_suffix(" { ... }")
+            .is_some_and(|signature| signature == declaration);
+
+        changes.push(ApiChange::changed(
+          path,
+          "method",
+          if unchanged_signature && *provided {
+            SemverImpact::Minor
+          } else {
+            SemverImpact::Major
+          },
+          Some(declaration),
+          Some(new_declaration),
+        ));
+      }
+      Some(_) => {}
+    }
+  }
+
+  for (path, (declaration, provided)) in &new {
+    if !old.contains_key(path) {
+      changes.push(ApiChange::added(
+        path,
+        "method",
+        if *provided {
+          SemverImpact::Minor
+        } else {
+          SemverImpact::Major
+        },
+        Some(declaration),
+      ));
+    }
+  }
+}
+
+/// Inherent methods are paired up by name. Trait impls, including auto-trait
+/// and blanket ones, are compared by their headers: losing one, say `Send`,
+/// breaks callers, while gaining one is an addition.
+fn diff_implementations(
+  path: &str,
+  old: &[Implementation],
+  new: &[Implementation],
+  changes: &mut Vec<ApiChange>,
+) {
+  let methods = |implementations: &[Implementation]| {
+    implementations
+      .iter()
+      .filter(|implementation| {
+        implementation.kind == ImplementationKind::Inherent
+      })
+      .flat_map(|implementation| &implementation.methods)
+      .map(|method| {
+        (
+          method.search_items().0.clone(),
+          normalize(method.signature().unwrap_or_default()),
+        )
+      })
+      .collect::<Vec<_>>()
+  };
+
+  diff_members(
+    path,
+    "method",
+    methods(old),
+    methods(new),
+    SemverImpact::Minor,
+    changes,
+  );
+
+  let trait_impls = |implementations: &[Implementation]| {
+    implementations
+      .iter()
+      .filter(|implementation| {
+        implementation.kind != ImplementationKind::Inherent
+      })
+      .map(|implementation| normalize(&implementation.signature))
+      .collect::<BTreeSet<_>>()
+  };
+
+  let (old, new) = (trait_impls(old), trait_impls(new));
+
+  for signature in old.difference(&new) {
+    changes.push(ApiChange::removed(path, "impl", Some(signature)));
+  }
+
+  for signature in new.difference(&old) {
+    changes.push(ApiChange::added(
+      path,
+      "impl",
+      SemverImpact::Minor,
+      Some(signature),
+    ));
+  }
+}
+
+/// Pair up the members of an item by name, given their declarations on each
+/// side. Removing or changing a member is breaking, while adding one has the
+/// given impact.
+fn diff_members(
+  path: &str,
+  item_type: &str,
+  old: Vec<(String, String)>,
+  new: Vec<(String, String)>,
+  added: SemverImpact,
+  changes: &mut Vec<ApiChange>,
+) {
+  let (old, new) = (
+    old.into_iter().collect::<BTreeMap<_, _>>(),
+    new.into_iter().collect::<BTreeMap<_, _>>(),
+  );
+
+  for (name, declaration) in &old {
+    let member = format!("{path}::{name}");
+
+    match new.get(name) {
+      None => {
+        changes.push(ApiChange::removed(&member, item_type, Some(declaration)));
+      }
+      Some(new_declaration)
+        if normalize(new_declaration) != normalize(declaration) =>
+      {
+        changes.push(ApiChange::changed(
+          &member,
+          item_type,
+          SemverImpact::Major,
+          Some(declaration),
+          Some(new_declaration),
+        ));
+      }
+      Some(_) => {}
+    }
+  }
+
+  for (name, declaration) in &new {
+    if !old.contains_key(name) {
+      changes.push(ApiChange::added(
+        &format!("{path}::{name}"),
+        item_type,
+        added,
+        Some(declaration),
+      ));
+    }
+  }
+}
+
+/// An item's declaration with whitespace collapsed. For types and traits,
+/// the body is dropped, since their fields, variants and methods are compared
+/// one by one. That is the braces, or the parentheses of a tuple struct,
+/// which its `where` clause follows.
+fn declaration(item: &Item) -> Option<String> {
+  let signature = item.signature()?;
+
+  if !matches!(
+    item.kind(),
+    ItemKind::Struct | ItemKind::Enum | ItemKind::Union | ItemKind::Trait
+  ) {
+    return Some(normalize(signature));
+  }
+
+  // Skip the visibility, name and generics, whose brackets aren't the body.
+  let start = regex!(r"\b(?:struct|enum|union|trait)\s+[A-Za-z0-9_]*\s*")
+    .find(signature)
+    .map_or(0, |keyword| keyword.end());
+
+  let (name, rest) = signature.split_at(start);
+
+  let (generics, rest) = split_generics(rest);
+
+  let rest = rest.trim_start();
+
+  let rest = if rest.starts_with('(') {
+    closing(rest).map_or(rest, |end| &rest[end + 1..])
+  } else {
+    rest
+  };
+
+  Some(normalize(
+    format!(
+      "{name}{generics} {}",
+      rest.split('{').next().unwrap_or(rest)
+    )
+    .trim_end()
+    .trim_end_matches(';'),
+  ))
+}
+
+fn variant_declaration(variant: &Variant) -> String {
+  let fields = match &variant.kind {
+    VariantKind::Unit => String::new(),
+    VariantKind::Tuple(types) => format!("({})", types.join(", ")),
+    VariantKind::Struct(fields) => format!(
+      " {{ {} }}",
+      fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.ty))
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+  };
+
+  match &variant.discriminant {
+    Some(discriminant) => format!("{}{fields} = {discriminant}", variant.name),
+    None => format!("{}{fields}", variant.name),
+  }
+}
+
+fn non_exhaustive(signature: &str) -> bool {
+  signature.contains("#[non_exhaustive]")
+}
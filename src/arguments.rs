@@ -2,12 +2,14 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Arguments {
+  #[clap(flatten)]
+  options: Options,
   #[clap(subcommand)]
   subcommand: Subcommand,
 }
 
 impl Arguments {
   pub(crate) async fn run(self) -> Result {
-    self.subcommand.run().await
+    self.subcommand.run(self.options.resolve()).await
   }
 }
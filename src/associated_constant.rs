@@ -0,0 +1,11 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AssociatedConstant {
+  pub name: String,
+  pub signature: String,
+  #[serde(rename = "type")]
+  pub ty: String,
+  pub default: Option<String>,
+  pub description: Option<String>,
+}
@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AssociatedType {
+  pub name: String,
+  pub signature: String,
+  pub bounds: Vec<String>,
+  pub default: Option<String>,
+  pub description: Option<String>,
+}
@@ -0,0 +1,360 @@
+use {
+  super::*,
+  std::{fs::File, time::UNIX_EPOCH},
+  tempfile::NamedTempFile,
+};
+
+pub const CACHE_DIRECTORY: &str = ".crates-mcp";
+
+/// Parsed items live apart from each crate's index, one shard per directory
+/// of pages, so that a lookup only reads the shards it needs.
+const SHARD_DIRECTORY: &str = "items";
+
+/// Bumped whenever the serialized `Item` model changes, so that stale caches
+/// are discarded instead of misread.
+const VERSION: u32 = 20;
+
+#[derive(Clone, Debug)]
+pub struct Cache {
+  directory: Option<PathBuf>,
+}
+
+impl Cache {
+  pub fn new(path: &str, enabled: bool) -> Self {
+    Self {
+      directory: enabled.then(|| PathBuf::from(path).join(CACHE_DIRECTORY)),
+    }
+  }
+
+  /// A cache for another documentation directory, enabled only if this one
+  /// is.
+  pub fn for_path(&self, path: &str) -> Self {
+    Self::new(path, self.directory.is_some())
+  }
+
+  /// The modification times and sizes of a crate's source files. Their items
+  /// are read from the shards on demand. The index holds a lock on the crate
+  /// until it is dropped, so that concurrent lookups, in this process or
+  /// another, don't interleave loading, merging and storing.
+  pub fn load(&self, crate_name: &str) -> CrateIndex {
+    let Some(directory) = &self.directory else {
+      return CrateIndex::default();
+    };
+
+    let lock = fs::create_dir_all(directory)
+      .and_then(|()| File::create(directory.join(format!("{crate_name}.lock"))))
+      .and_then(|file| file.lock().map(|()| file))
+      .inspect_err(|error| {
+        warn!("failed to lock index cache for '{crate_name}': {error}");
+      })
+      .ok();
+
+    let mut index =
+      fs::read_to_string(directory.join(format!("{crate_name}.json")))
+        .ok()
+        .and_then(|content| serde_json::from_str::<CrateIndex>(&content).ok())
+        .filter(|index| index.version == VERSION)
+        .unwrap_or_default();
+
+    index.shards = Some(directory.join(SHARD_DIRECTORY).join(crate_name));
+    index.lock = lock;
+
+    index
+  }
+
+  /// Write the shards touched since loading, then the index itself.
+  pub fn store(&self, crate_name: &str, index: &CrateIndex) -> Result {
+    let Some(directory) = &self.directory else {
+      return Ok(());
+    };
+
+    if index.dirty.is_empty() {
+      return Ok(());
+    }
+
+    let shards = directory.join(SHARD_DIRECTORY).join(crate_name);
+
+    fs::create_dir_all(&shards)?;
+
+    for shard in &index.dirty {
+      let path = shards.join(format!("{shard}.json"));
+
+      let stored = read_shard(&path);
+
+      let items = stored
+        .iter()
+        .filter(|(file, _)| {
+          index.sources.contains_key(*file) && !index.parsed.contains_key(*file)
+        })
+        .chain(
+          index
+            .parsed
+            .iter()
+            .filter(|(file, _)| shard_name(file) == *shard),
+        )
+        .collect::<BTreeMap<_, _>>();
+
+      if items.is_empty() {
+        fs::remove_file(path).ok();
+      } else {
+        write_atomically(&path, &items)?;
+      }
+    }
+
+    write_atomically(&directory.join(format!("{crate_name}.json")), index)
+  }
+
+  pub fn clear(&self) -> Result<usize> {
+    let Some(directory) = &self.directory else {
+      return Ok(0);
+    };
+
+    if !directory.exists() {
+      return Ok(0);
+    }
+
+    let mut count = 0;
+
+    // Snapshots live in a directory beside the indexes and are kept.
+    for entry in fs::read_dir(directory)? {
+      let path = entry?.path();
+
+      if path.is_file() {
+        count += usize::from(path.extension().is_some_and(|ext| ext == "json"));
+        fs::remove_file(path)?;
+      }
+    }
+
+    let shards = directory.join(SHARD_DIRECTORY);
+
+    if shards.exists() {
+      fs::remove_dir_all(shards)?;
+    }
+
+    fs::remove_dir(directory).ok();
+
+    Ok(count)
+  }
+}
+
+fn read_shard(path: &Path) -> BTreeMap<PathBuf, Vec<Item>> {
+  fs::read_to_string(path)
+    .ok()
+    .and_then(|content| serde_json::from_str(&content).ok())
+    .unwrap_or_default()
+}
+
+/// Write through a uniquely named temporary file, so that readers never see
+/// a partial file and writers never share one.
+fn write_atomically(path: &Path, value: &impl Serialize) -> Result {
+  let mut temporary =
+    NamedTempFile::new_in(path.parent().unwrap_or(Path::new(".")))?;
+
+  serde_json::to_writer(&mut temporary, value)?;
+
+  temporary.persist(path).map_err(|error| error.error)?;
+
+  Ok(())
+}
+
+/// The shard a source file's items are kept in, named after a hash of its
+/// directory, which is stable across runs unlike the standard library's.
+fn shard_name(file: &Path) -> String {
+  let directory = file.parent().unwrap_or(file).as_os_str();
+
+  let hash = directory
+    .as_encoded_bytes()
+    .iter()
+    .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+      (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+  format!("{hash:016x}")
+}
+
+/// The source files of one crate along with their modification time and
+/// size, and the items parsed from them since the index was loaded.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CrateIndex {
+  version: u32,
+  sources: BTreeMap<PathBuf, Source>,
+  #[serde(skip)]
+  shards: Option<PathBuf>,
+  #[serde(skip)]
+  parsed: BTreeMap<PathBuf, Vec<Item>>,
+  #[serde(skip)]
+  dirty: BTreeSet<String>,
+  #[serde(skip)]
+  lock: Option<File>,
+}
+
+impl Default for CrateIndex {
+  fn default() -> Self {
+    Self {
+      version: VERSION,
+      sources: BTreeMap::new(),
+      shards: None,
+      parsed: BTreeMap::new(),
+      dirty: BTreeSet::new(),
+      lock: None,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Source {
+  modified: u64,
+  size: u64,
+}
+
+impl Source {
+  fn stamp(file: &Path) -> Result<Self> {
+    let metadata = fs::metadata(file)?;
+
+    let modified = metadata
+      .modified()?
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_nanos() as u64)
+      .unwrap_or_default();
+
+    Ok(Self {
+      modified,
+      size: metadata.len(),
+    })
+  }
+}
+
+impl CrateIndex {
+  pub fn items(
+    &mut self,
+    file: &Path,
+    parse: impl FnOnce(&Path) -> Result<Vec<Item>>,
+  ) -> Result<Vec<Item>> {
+    let stamp = Source::stamp(file)?;
+
+    let mut cached = self.cached(&[(file, &stamp)]);
+
+    if let Some(items) = cached.remove(file) {
+      return Ok(items);
+    }
+
+    let items = parse(file)?;
+
+    self.insert(file, stamp, &items);
+
+    Ok(items)
+  }
+
+  /// Like `items`, for the pages of many entries at once. Pages missing from
+  /// the cache are parsed on rayon's thread pool, and results come back in
+  /// the order of `entries`.
+  pub fn par_items(
+    &mut self,
+    entries: &[&Entry],
+    parse: impl Fn(&Entry) -> Result<Vec<Item>> + Sync,
+  ) -> Result<Vec<Vec<Item>>> {
+    let stamps = entries
+      .par_iter()
+      .map(|entry| Source::stamp(&entry.file))
+      .collect::<Result<Vec<_>>>()?;
+
+    let mut cached = self.cached(
+      &entries
+        .iter()
+        .zip(&stamps)
+        .map(|(entry, stamp)| (entry.file.as_path(), stamp))
+        .collect::<Vec<_>>(),
+    );
+
+    let results = entries
+      .iter()
+      .map(|entry| cached.remove(&entry.file))
+      .collect::<Vec<_>>()
+      .into_par_iter()
+      .zip(entries)
+      .map(|(items, entry)| match items {
+        Some(items) => Ok((items, false)),
+        None => Ok((parse(entry)?, true)),
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Ok(
+      entries
+        .iter()
+        .zip(stamps)
+        .zip(results)
+        .map(|((entry, stamp), (items, parsed))| {
+          if parsed {
+            self.insert(&entry.file, stamp, &items);
+          }
+
+          items
+        })
+        .collect(),
+    )
+  }
+
+  /// The items of those files whose stamps still match, read from their
+  /// shards once each and moved out rather than copied.
+  fn cached(&self, files: &[(&Path, &Source)]) -> HashMap<PathBuf, Vec<Item>> {
+    let mut wanted = BTreeMap::<String, Vec<&Path>>::new();
+
+    let mut cached = HashMap::new();
+
+    for &(file, stamp) in files {
+      if self.sources.get(file) != Some(stamp) {
+        continue;
+      }
+
+      match self.parsed.get(file) {
+        Some(items) => {
+          cached.insert(file.to_path_buf(), items.clone());
+        }
+        None => wanted.entry(shard_name(file)).or_default().push(file),
+      }
+    }
+
+    let Some(shards) = &self.shards else {
+      return cached;
+    };
+
+    for (shard, files) in wanted {
+      let mut items = read_shard(&shards.join(format!("{shard}.json")));
+
+      for file in files {
+        if let Some(items) = items.remove(file) {
+          cached.insert(file.to_path_buf(), items);
+        }
+      }
+    }
+
+    cached
+  }
+
+  fn insert(&mut self, file: &Path, stamp: Source, items: &[Item]) {
+    self.sources.insert(file.to_path_buf(), stamp);
+
+    // Without a cache directory nothing is stored, so there is no need to
+    // hold on to a copy.
+    if self.shards.is_some() {
+      self.parsed.insert(file.to_path_buf(), items.to_vec());
+    }
+
+    self.dirty.insert(shard_name(file));
+  }
+
+  pub fn retain(&mut self, files: &HashSet<&Path>) {
+    let removed = self
+      .sources
+      .keys()
+      .filter(|file| !files.contains(file.as_path()))
+      .cloned()
+      .collect::<Vec<_>>();
+
+    for file in removed {
+      self.sources.remove(&file);
+      self.parsed.remove(&file);
+      self.dirty.insert(shard_name(&file));
+    }
+  }
+}
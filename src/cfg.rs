@@ -0,0 +1,363 @@
+use super::*;
+
+/// The cfg expression behind a portability badge, such as
+/// `all(feature = "rt", unix)` for "crate feature `rt` and Unix", recovered by
+/// inverting the way rustdoc renders it. Code spans must be kept in
+/// backticks. Conditions rustdoc has no wording for are rendered as code, so
+/// anything else yields `None`.
+pub fn portability_cfg(condition: &str) -> Option<String> {
+  let mut parser = Conditions {
+    tokens: tokenize(condition)?,
+    position: 0,
+    shorthand: None,
+  };
+
+  let cfg = parser.list()?;
+
+  (parser.position == parser.tokens.len()).then_some(cfg)
+}
+
+/// The expression of a `#[cfg(...)]` or `#[doc(cfg(...))]` attribute, such as
+/// `all(unix, feature = "x")`.
+pub fn attribute_cfg(attribute: &str) -> Option<String> {
+  let inner = |text: &str, name: &str| {
+    let arguments = text.trim().strip_prefix(name)?.trim_start();
+
+    (arguments.starts_with('(') && closing(arguments)? == arguments.len() - 1)
+      .then(|| arguments[1..arguments.len() - 1].trim().to_string())
+  };
+
+  let attribute = attribute.strip_prefix("#[")?.strip_suffix(']')?;
+
+  let attribute = inner(attribute, "doc").unwrap_or_else(|| attribute.into());
+
+  inner(&attribute, "cfg")
+}
+
+/// The crate features a cfg expression requires, leaving out those it only
+/// mentions under `not(...)`.
+pub fn cfg_features(cfg: &str) -> Vec<String> {
+  let mut required = cfg.to_string();
+
+  while let Some(start) = regex!(r"\bnot\s*\(").find(&required) {
+    let open = start.end() - 1;
+
+    let end =
+      closing(&required[open..]).map_or(required.len(), |end| open + end + 1);
+
+    required.replace_range(start.start()..end, "");
+  }
+
+  regex!(r#"\bfeature\s*=\s*"([^"]+)""#)
+    .captures_iter(&required)
+    .map(|captures| captures[1].to_string())
+    .collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+  Code(String),
+  Comma,
+  Close,
+  Non,
+  Open,
+  Word(String),
+}
+
+fn tokenize(condition: &str) -> Option<Vec<Token>> {
+  let mut tokens = Vec::new();
+
+  let mut rest = condition.trim();
+
+  while !rest.is_empty() {
+    if let Some(code) = rest.strip_prefix('`') {
+      let (code, after) = code.split_once('`')?;
+      tokens.push(Token::Code(code.to_string()));
+      rest = after;
+    } else if let Some(after) = rest.strip_prefix("non-") {
+      tokens.push(Token::Non);
+      rest = after;
+    } else {
+      let c = rest.chars().next()?;
+
+      let length = match c {
+        '(' => {
+          tokens.push(Token::Open);
+          1
+        }
+        ')' => {
+          tokens.push(Token::Close);
+          1
+        }
+        ',' => {
+          tokens.push(Token::Comma);
+          1
+        }
+        _ if c.is_whitespace() => c.len_utf8(),
+        _ => {
+          let length = rest
+            .find(|c: char| c.is_whitespace() || "(),`".contains(c))
+            .unwrap_or(rest.len());
+          tokens.push(Token::Word(rest[..length].to_string()));
+          length
+        }
+      };
+
+      rest = &rest[length..];
+    }
+  }
+
+  Some(tokens)
+}
+
+struct Conditions {
+  tokens: Vec<Token>,
+  position: usize,
+  /// Set by "crate features" or "target features", which rustdoc puts once
+  /// in front of a list of bare feature names.
+  shorthand: Option<&'static str>,
+}
+
+impl Conditions {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.position)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.position).cloned();
+    self.position += 1;
+    token
+  }
+
+  fn eat(&mut self, token: &Token) -> bool {
+    let matches = self.peek() == Some(token);
+    self.position += usize::from(matches);
+    matches
+  }
+
+  fn eat_word(&mut self, word: &str) -> bool {
+    self.eat(&Token::Word(word.to_string()))
+  }
+
+  /// Conditions joined by "and" or "or", with commas between them when there
+  /// are more than two. rustdoc doesn't parenthesize an "and" inside an "or",
+  /// so the former binds tighter.
+  fn list(&mut self) -> Option<String> {
+    let shorthand = self.shorthand.take();
+
+    let mut conditions = vec![self.condition()?];
+
+    // Whether each condition after the first follows an "and", an "or" or
+    // just a comma.
+    let mut separators = Vec::new();
+
+    loop {
+      let comma = self.eat(&Token::Comma);
+
+      separators.push(if self.eat_word("and") {
+        Some("and")
+      } else if self.eat_word("or") {
+        Some("or")
+      } else if comma {
+        None
+      } else {
+        break;
+      });
+
+      conditions.push(self.condition()?);
+    }
+
+    self.shorthand = shorthand;
+
+    if !separators.contains(&Some("or")) {
+      return match conditions.len() {
+        1 => conditions.pop(),
+        _ if separators.contains(&Some("and")) => {
+          Some(format!("all({})", conditions.join(", ")))
+        }
+        _ => None,
+      };
+    }
+
+    let mut alternatives = vec![vec![conditions.remove(0)]];
+
+    for (separator, condition) in separators.into_iter().zip(conditions) {
+      if separator == Some("and") {
+        alternatives.last_mut()?.push(condition);
+      } else {
+        alternatives.push(vec![condition]);
+      }
+    }
+
+    let mut alternatives = alternatives
+      .into_iter()
+      .map(|mut group| match group.len() {
+        1 => group.pop(),
+        _ => Some(format!("all({})", group.join(", "))),
+      })
+      .collect::<Option<Vec<_>>>()?;
+
+    match alternatives.len() {
+      1 => alternatives.pop(),
+      _ => Some(format!("any({})", alternatives.join(", "))),
+    }
+  }
+
+  fn condition(&mut self) -> Option<String> {
+    match self.next()? {
+      Token::Open => {
+        let cfg = self.list()?;
+        self.eat(&Token::Close).then_some(cfg)
+      }
+      Token::Non => Some(format!("not({})", self.condition()?)),
+      Token::Code(code) => Some(match self.shorthand {
+        Some(name) => format!("{name} = \"{code}\""),
+        None => match code.split_once('=') {
+          Some((name, value)) => {
+            format!("{} = \"{}\"", name.trim(), value.trim().trim_matches('"'))
+          }
+          None => code,
+        },
+      }),
+      Token::Word(word) if word == "neither" => {
+        let mut conditions = vec![self.condition()?];
+
+        loop {
+          let comma = self.eat(&Token::Comma);
+
+          if !self.eat_word("nor") {
+            self.position -= usize::from(comma);
+            break;
+          }
+
+          conditions.push(self.condition()?);
+        }
+
+        Some(format!("not(any({}))", conditions.join(", ")))
+      }
+      Token::Word(word) if word == "not" && self.eat(&Token::Open) => {
+        let cfg = self.list()?;
+        self.eat(&Token::Close).then(|| format!("not({cfg})"))
+      }
+      Token::Word(word) if word == "crate" || word == "target" => {
+        let name = if word == "crate" {
+          "feature"
+        } else {
+          "target_feature"
+        };
+
+        if self.eat_word("features") {
+          self.shorthand = Some(name);
+          return self.condition();
+        }
+
+        if !self.eat_word("feature") {
+          return None;
+        }
+
+        match self.next()? {
+          Token::Code(code) => Some(format!("{name} = \"{code}\"")),
+          _ => None,
+        }
+      }
+      Token::Word(word) => {
+        let mut phrase = word;
+
+        while let Some(Token::Word(word)) = self.peek()
+          && !["and", "or", "nor"].contains(&word.as_str())
+        {
+          phrase = format!("{phrase} {word}");
+          self.position += 1;
+        }
+
+        named(&phrase)
+      }
+      Token::Close | Token::Comma => None,
+    }
+  }
+}
+
+/// The cfg rustdoc gives a human readable name, such as `target_os = "macos"`
+/// for "macOS".
+fn named(name: &str) -> Option<String> {
+  if let Some(endian) = name.strip_suffix("-endian") {
+    return Some(format!("target_endian = \"{endian}\""));
+  }
+
+  if let Some(bits) = name
+    .strip_suffix("-bit")
+    .filter(|bits| bits.chars().all(|c| c.is_ascii_digit()))
+  {
+    return Some(format!("target_pointer_width = \"{bits}\""));
+  }
+
+  let (key, value) = match name {
+    "Unix" => return Some("unix".to_string()),
+    "Windows" => return Some("windows".to_string()),
+    "debug-assertions enabled" => return Some("debug_assertions".to_string()),
+    "AIX" => ("target_os", "aix"),
+    "Android" => ("target_os", "android"),
+    "Cygwin" => ("target_os", "cygwin"),
+    "DragonFly BSD" => ("target_os", "dragonfly"),
+    "Emscripten" => ("target_os", "emscripten"),
+    "FreeBSD" => ("target_os", "freebsd"),
+    "Fuchsia" => ("target_os", "fuchsia"),
+    "Haiku" => ("target_os", "haiku"),
+    "Hermit" | "HermitCore" => ("target_os", "hermit"),
+    "illumos" => ("target_os", "illumos"),
+    "iOS" => ("target_os", "ios"),
+    "L4Re" => ("target_os", "l4re"),
+    "Linux" => ("target_os", "linux"),
+    "macOS" => ("target_os", "macos"),
+    "Motor OS" => ("target_os", "motor"),
+    "NetBSD" => ("target_os", "netbsd"),
+    "OpenBSD" => ("target_os", "openbsd"),
+    "QNX Neutrino" => ("target_os", "nto"),
+    "Redox" => ("target_os", "redox"),
+    "Solaris" => ("target_os", "solaris"),
+    "Trusty" => ("target_os", "trusty"),
+    "tvOS" => ("target_os", "tvos"),
+    "visionOS" => ("target_os", "visionos"),
+    "WASI" => ("target_os", "wasi"),
+    "watchOS" => ("target_os", "watchos"),
+    "AArch64" => ("target_arch", "aarch64"),
+    "AMD GPU" => ("target_arch", "amdgpu"),
+    "ARM" => ("target_arch", "arm"),
+    "ARM64EC" => ("target_arch", "arm64ec"),
+    "CSKY" => ("target_arch", "csky"),
+    "Hexagon" => ("target_arch", "hexagon"),
+    "LoongArch32" => ("target_arch", "loongarch32"),
+    "LoongArch64" | "LoongArch LA64" => ("target_arch", "loongarch64"),
+    "M68k" => ("target_arch", "m68k"),
+    "MIPS" => ("target_arch", "mips"),
+    "MIPS Release 6" => ("target_arch", "mips32r6"),
+    "MIPS-64" => ("target_arch", "mips64"),
+    "MIPS-64 Release 6" => ("target_arch", "mips64r6"),
+    "MSP430" => ("target_arch", "msp430"),
+    "NVidia GPU" => ("target_arch", "nvptx64"),
+    "PowerPC" => ("target_arch", "powerpc"),
+    "PowerPC64" | "PowerPC-64" => ("target_arch", "powerpc64"),
+    "RISC-V RV32" => ("target_arch", "riscv32"),
+    "RISC-V RV64" => ("target_arch", "riscv64"),
+    "s390x" => ("target_arch", "s390x"),
+    "SPARC64" => ("target_arch", "sparc64"),
+    "WebAssembly" => ("target_arch", "wasm32"),
+    "x86" => ("target_arch", "x86"),
+    "x86-64" => ("target_arch", "x86_64"),
+    "Apple" => ("target_vendor", "apple"),
+    "Fortanix" => ("target_vendor", "fortanix"),
+    "PC" => ("target_vendor", "pc"),
+    "Sun" => ("target_vendor", "sun"),
+    "GNU" => ("target_env", "gnu"),
+    "MSVC" => ("target_env", "msvc"),
+    "musl" => ("target_env", "musl"),
+    "Newlib" => ("target_env", "newlib"),
+    "SGX" => ("target_env", "sgx"),
+    "uClibc" => ("target_env", "uclibc"),
+    "WASIp1" => ("target_env", "p1"),
+    "WASIp2" => ("target_env", "p2"),
+    _ => return None,
+  };
+
+  Some(format!("{key} = \"{value}\""))
+}
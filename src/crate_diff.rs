@@ -0,0 +1,12 @@
+use super::*;
+
+/// The API changes between two documented versions of a crate, and the
+/// semver impact of the most disruptive one.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct CrateDiff {
+  pub name: String,
+  pub old_version: Option<String>,
+  pub new_version: Option<String>,
+  pub impact: SemverImpact,
+  pub changes: Vec<ApiChange>,
+}
@@ -0,0 +1,250 @@
+use super::*;
+
+/// The parameters declared between the angle brackets of a function or impl,
+/// e.g. `'a, T: Clone + 'a, const N: usize`.
+pub fn generic_parameters(generics: &str) -> Vec<GenericParameter> {
+  split_top_level(generics, ',')
+    .into_iter()
+    .map(generic_parameter)
+    .collect()
+}
+
+fn generic_parameter(parameter: &str) -> GenericParameter {
+  let (parameter, default) =
+    match top_level(parameter).into_iter().find(|(_, c)| *c == '=') {
+      Some((index, _)) => (
+        &parameter[..index],
+        Some(normalize(&parameter[index + 1..])),
+      ),
+      None => (parameter, None),
+    };
+
+  if let Some(parameter) = parameter.trim().strip_prefix("const ") {
+    let (name, ty) = split_bound(parameter);
+
+    return GenericParameter {
+      name: normalize(name),
+      kind: GenericKind::Const,
+      bounds: Vec::new(),
+      ty: ty.map(normalize),
+      default,
+    };
+  }
+
+  let (name, bounds) = split_bound(parameter);
+
+  GenericParameter {
+    kind: if name.starts_with('\'') {
+      GenericKind::Lifetime
+    } else {
+      GenericKind::Type
+    },
+    name: normalize(name),
+    bounds: bounds.map(split_bounds).unwrap_or_default(),
+    ty: None,
+    default,
+  }
+}
+
+/// The predicates of a `where` clause, such as `T: Clone, U: Into<T>`.
+pub fn where_predicates(predicates: &str) -> Vec<WherePredicate> {
+  split_top_level(predicates, ',')
+    .into_iter()
+    .map(|predicate| {
+      let (ty, bounds) = split_bound(predicate);
+
+      WherePredicate {
+        ty: normalize(ty),
+        bounds: bounds.map(split_bounds).unwrap_or_default(),
+      }
+    })
+    .collect()
+}
+
+/// The characters of `text` outside of any brackets, along with their byte
+/// offsets. The `>` of an `->` arrow doesn't close anything.
+fn top_level(text: &str) -> Vec<(usize, char)> {
+  let (mut depth, mut previous, mut characters) = (0usize, ' ', Vec::new());
+
+  for (index, c) in text.char_indices() {
+    match c {
+      '<' | '(' | '[' | '{' => depth += 1,
+      '>' if previous == '-' && depth > 0 => {}
+      '>' if previous == '-' => characters.push((index, c)),
+      '>' | ')' | ']' | '}' => depth = depth.saturating_sub(1),
+      _ if depth == 0 => characters.push((index, c)),
+      _ => {}
+    }
+
+    previous = c;
+  }
+
+  characters
+}
+
+/// The offset of the bracket that closes the one `text` starts with.
+pub fn closing(text: &str) -> Option<usize> {
+  let (mut depth, mut previous) = (0usize, ' ');
+
+  for (index, c) in text.char_indices() {
+    match c {
+      '<' | '(' | '[' | '{' => depth += 1,
+      '>' if previous == '-' => {}
+      '>' | ')' | ']' | '}' => {
+        depth = depth.checked_sub(1)?;
+
+        if depth == 0 {
+          return Some(index);
+        }
+      }
+      _ => {}
+    }
+
+    previous = c;
+  }
+
+  None
+}
+
+/// Split a list on `separator`, ignoring separators nested inside brackets,
+/// parentheses or generic arguments, and dropping empty parts.
+pub fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+  let mut start = 0;
+
+  let mut parts = Vec::new();
+
+  for (index, c) in top_level(text) {
+    if c == separator {
+      parts.push(&text[start..index]);
+      start = index + c.len_utf8();
+    }
+  }
+
+  parts.push(&text[start..]);
+
+  parts
+    .into_iter()
+    .map(str::trim)
+    .filter(|part| !part.is_empty())
+    .collect()
+}
+
+/// Split `T: Bound` or `name: Type` at its first top-level colon, skipping
+/// those of `::` path separators.
+pub fn split_bound(text: &str) -> (&str, Option<&str>) {
+  let bytes = text.as_bytes();
+
+  top_level(text)
+    .into_iter()
+    .find(|&(index, c)| {
+      c == ':'
+        && bytes.get(index + 1) != Some(&b':')
+        && (index == 0 || bytes[index - 1] != b':')
+    })
+    .map(|(index, _)| (text[..index].trim(), Some(text[index + 1..].trim())))
+    .unwrap_or((text.trim(), None))
+}
+
+fn split_bounds(bounds: &str) -> Vec<String> {
+  split_top_level(bounds, '+')
+    .into_iter()
+    .map(normalize)
+    .collect()
+}
+
+/// Split a leading `<...>` generic parameter list off a declaration.
+pub fn split_generics(declaration: &str) -> (&str, &str) {
+  if !declaration.starts_with('<') {
+    return ("", declaration);
+  }
+
+  match closing(declaration) {
+    Some(end) => declaration.split_at(end + 1),
+    None => (declaration, ""),
+  }
+}
+
+/// The offset of a top-level `where` keyword, which rustdoc's rendered text
+/// doesn't always separate from what precedes it with a space.
+pub fn find_where(text: &str) -> Option<usize> {
+  let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+
+  top_level(text)
+    .into_iter()
+    .map(|(index, _)| index)
+    .find(|&index| {
+      text[index..].starts_with("where")
+        && !text[..index].ends_with(is_identifier)
+        && !text[index + "where".len()..].starts_with(is_identifier)
+    })
+}
+
+/// Split a declaration at a trailing `where` clause, returning the part
+/// before it and the clause's predicates.
+pub fn split_where_clause(declaration: &str) -> (&str, Option<&str>) {
+  match find_where(declaration) {
+    Some(index) => (
+      &declaration[..index],
+      Some(&declaration[index + "where".len()..]),
+    ),
+    None => (declaration, None),
+  }
+}
+
+/// Split an impl header such as `impl<T: Clone> From<T> for Wrapper<T>` into
+/// its generic parameter list, the implemented trait, if any, the
+/// implementing type and the `where` clause.
+pub fn split_impl_header(
+  signature: &str,
+) -> Option<(&str, Option<&str>, &str, Option<&str>)> {
+  let header = signature
+    .trim_start_matches("unsafe ")
+    .strip_prefix("impl")?;
+
+  let (generics, header) = split_generics(header);
+
+  if !header.starts_with(char::is_whitespace) {
+    return None;
+  }
+
+  let (header, where_clause) = split_where_clause(header);
+
+  let header = header.trim();
+
+  let (trait_name, ty) = match top_level(header)
+    .into_iter()
+    .find(|&(index, _)| index > 0 && header[index..].starts_with(" for "))
+  {
+    Some((index, _)) => (
+      Some(header[..index].trim()),
+      &header[index + " for ".len()..],
+    ),
+    None => (None, header),
+  };
+
+  Some((generics, trait_name, ty.trim(), where_clause))
+}
+
+/// The generic parameters and `where` predicates of an impl header.
+pub fn impl_generics(
+  signature: &str,
+) -> (Vec<GenericParameter>, Vec<WherePredicate>) {
+  let Some((generics, _, _, where_clause)) = split_impl_header(signature)
+  else {
+    return (Vec::new(), Vec::new());
+  };
+
+  (
+    generic_parameters(
+      generics
+        .strip_prefix('<')
+        .and_then(|generics| generics.strip_suffix('>'))
+        .unwrap_or_default(),
+    ),
+    where_clause.map(where_predicates).unwrap_or_default(),
+  )
+}
+
+pub fn normalize(text: &str) -> String {
+  text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
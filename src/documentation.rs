@@ -1,7 +1,20 @@
 use super::*;
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Items of a crate, as returned by `lookup_crate`, along with the target they
+/// were documented for when not the host. A crate overview fills in the
+/// version, root docs, top-level modules and item counts instead.
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Documentation {
   pub name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub target: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub version: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub modules: Option<Vec<ModuleSummary>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub item_counts: Option<BTreeMap<String, usize>>,
   pub items: Vec<Item>,
 }
@@ -0,0 +1,24 @@
+use super::*;
+
+/// A documentation page discovered without parsing it, along with the
+/// one-line description from rustdoc's search index when available, and the
+/// paths of any redirect pages pointing at it.
+#[derive(Clone, Debug)]
+pub struct Entry {
+  pub aliases: Vec<String>,
+  pub description: Option<String>,
+  pub file: PathBuf,
+  pub kind: ItemKind,
+  pub module: String,
+  pub name: String,
+}
+
+impl Entry {
+  pub fn path(&self) -> String {
+    if self.module.is_empty() {
+      self.name.clone()
+    } else {
+      format!("{}::{}", self.module, self.name)
+    }
+  }
+}
@@ -0,0 +1,16 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Example {
+  pub heading: Option<String>,
+  pub language: String,
+  pub attributes: Vec<String>,
+  pub code: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExampleMatch {
+  pub path: String,
+  #[serde(flatten)]
+  pub example: Example,
+}
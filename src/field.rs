@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Field {
+  pub name: String,
+  #[serde(rename = "type")]
+  pub ty: String,
+  pub visibility: String,
+  pub description: Option<String>,
+}
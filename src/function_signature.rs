@@ -0,0 +1,160 @@
+use super::*;
+
+/// The parts of a function or method declaration, parsed from the signature
+/// rustdoc renders for it.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FunctionSignature {
+  pub name: String,
+  #[serde(rename = "async")]
+  pub is_async: bool,
+  #[serde(rename = "const")]
+  pub is_const: bool,
+  #[serde(rename = "unsafe")]
+  pub is_unsafe: bool,
+  pub abi: Option<String>,
+  pub generics: Vec<GenericParameter>,
+  pub receiver: Option<Receiver>,
+  pub parameters: Vec<Parameter>,
+  pub return_type: Option<String>,
+  pub where_clause: Vec<WherePredicate>,
+}
+
+impl FunctionSignature {
+  /// Parse a declaration such as `pub const unsafe fn get<T: Copy>(&self,
+  /// index: usize) -> Option<T> where T: Send`, with or without attributes,
+  /// visibility and a trailing `;` or `{ ... }`. Anything that isn't a
+  /// function yields `None`.
+  pub fn parse(signature: &str) -> Option<Self> {
+    let mut parsed = Self::default();
+
+    let mut rest = signature.trim();
+
+    while rest.starts_with("#[") {
+      rest = rest[closing(&rest[1..])? + 2..].trim_start();
+    }
+
+    loop {
+      let (word, after) = word(rest);
+
+      rest = after.trim_start();
+
+      match word {
+        "pub" if rest.starts_with('(') => {
+          rest = rest[closing(rest)? + 1..].trim_start();
+        }
+        "pub" | "default" | "safe" => {}
+        "async" => parsed.is_async = true,
+        "const" => parsed.is_const = true,
+        "unsafe" => parsed.is_unsafe = true,
+        "extern" => {
+          parsed.abi = Some(match rest.strip_prefix('"') {
+            Some(quoted) => {
+              let (abi, after) = quoted.split_once('"')?;
+              rest = after.trim_start();
+              abi.to_string()
+            }
+            None => "C".to_string(),
+          });
+        }
+        "fn" => break,
+        _ => return None,
+      }
+    }
+
+    let (name, after) = word(rest.strip_prefix("r#").unwrap_or(rest));
+
+    if name.is_empty() {
+      return None;
+    }
+
+    parsed.name = name.to_string();
+
+    rest = after.trim_start();
+
+    if rest.starts_with('<') {
+      let end = closing(rest)?;
+
+      parsed.generics = generic_parameters(&rest[1..end]);
+
+      rest = rest[end + 1..].trim_start();
+    }
+
+    if !rest.starts_with('(') {
+      return None;
+    }
+
+    let end = closing(rest)?;
+
+    for parameter in split_top_level(&rest[1..end], ',') {
+      match receiver(parameter) {
+        Some(receiver) => parsed.receiver = Some(receiver),
+        None => {
+          let (name, ty) = split_bound(parameter);
+
+          parsed.parameters.push(Parameter {
+            name: normalize(name),
+            ty: ty.map(normalize).unwrap_or_default(),
+          });
+        }
+      }
+    }
+
+    rest = rest[end + 1..]
+      .trim_end()
+      .trim_end_matches(';')
+      .trim_end_matches("{ ... }")
+      .trim();
+
+    let (output, where_clause) = split_where_clause(rest);
+
+    parsed.return_type = output
+      .trim()
+      .strip_prefix("->")
+      .map(normalize)
+      .filter(|ty| !ty.is_empty());
+
+    parsed.where_clause =
+      where_clause.map(where_predicates).unwrap_or_default();
+
+    Some(parsed)
+  }
+}
+
+fn receiver(parameter: &str) -> Option<Receiver> {
+  let parameter = normalize(parameter);
+
+  // Drop the lifetime from `&'a self` and `&'a mut self`.
+  let parameter = match parameter.strip_prefix("&'") {
+    Some(rest) => format!("&{}", rest.split_once(' ')?.1),
+    None => parameter,
+  };
+
+  match parameter.as_str() {
+    "self" | "mut self" => Some(Receiver::Value),
+    "&self" => Some(Receiver::Ref),
+    "&mut self" => Some(Receiver::RefMut),
+    _ => {
+      let (name, ty) = split_bound(&parameter);
+
+      if name != "self" && name != "mut self" {
+        return None;
+      }
+
+      Some(match ty? {
+        "Self" => Receiver::Value,
+        "&Self" => Receiver::Ref,
+        "&mut Self" => Receiver::RefMut,
+        ty => Receiver::Typed(ty.to_string()),
+      })
+    }
+  }
+}
+
+/// The leading identifier or keyword of `text`, and what follows it.
+fn word(text: &str) -> (&str, &str) {
+  text.split_at(
+    text
+      .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+      .unwrap_or(text.len()),
+  )
+}
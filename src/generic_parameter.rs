@@ -0,0 +1,22 @@
+use super::*;
+
+/// A lifetime, type or const parameter of a function, with the bounds
+/// declared inline (those in the `where` clause are kept separately), its
+/// type if it is a const parameter, and its default if any.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GenericParameter {
+  pub name: String,
+  pub kind: GenericKind,
+  pub bounds: Vec<String>,
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub ty: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub default: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum GenericKind {
+  Lifetime,
+  Type,
+  Const,
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Implementation {
+  pub signature: String,
+  #[serde(rename = "trait")]
+  pub trait_name: Option<String>,
+  pub generics: Vec<GenericParameter>,
+  pub where_clause: Vec<WherePredicate>,
+  pub kind: ImplementationKind,
+  pub methods: Vec<Item>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ImplementationKind {
+  Inherent,
+  Trait,
+  Auto,
+  Blanket,
+}
+
+impl ImplementationKind {
+  /// Auto-trait and blanket impls are generated by the compiler or by generic
+  /// impls elsewhere, and are rarely what a caller is looking for.
+  pub fn is_synthetic(self) -> bool {
+    matches!(self, Self::Auto | Self::Blanket)
+  }
+}
@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Implementor {
+  #[serde(rename = "crate")]
+  pub crate_name: String,
+  pub signature: String,
+  #[serde(rename = "type")]
+  pub ty: String,
+}
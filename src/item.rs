@@ -1,77 +1,641 @@
 use super::*;
 
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum Item {
   Function {
     name: String,
+    path: String,
     signature: String,
+    parsed_signature: Option<FunctionSignature>,
     description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
   },
   Struct {
     name: String,
+    path: String,
     signature: String,
     description: Option<String>,
-    methods: Vec<Item>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    fields: Vec<Field>,
+    implementations: Vec<Implementation>,
   },
   Enum {
     name: String,
+    path: String,
     signature: String,
     description: Option<String>,
-    variants: Vec<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    variants: Vec<Variant>,
+    implementations: Vec<Implementation>,
   },
   Trait {
     name: String,
+    path: String,
     signature: String,
     description: Option<String>,
-    methods: Vec<Item>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    supertraits: Vec<String>,
+    required_methods: Vec<Item>,
+    provided_methods: Vec<Item>,
+    associated_types: Vec<AssociatedType>,
+    associated_constants: Vec<AssociatedConstant>,
+    dyn_compatible: bool,
+    implementors: Vec<Implementor>,
   },
   Macro {
     name: String,
+    path: String,
     signature: String,
     description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
   },
   Type {
     name: String,
+    path: String,
     signature: String,
     description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
   },
   Constant {
     name: String,
+    path: String,
     signature: String,
     description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
   },
   Module {
     name: String,
+    path: String,
     description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
     items: Vec<String>,
+    reexports: Vec<ReExport>,
+  },
+  Union {
+    name: String,
+    path: String,
+    signature: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    fields: Vec<Field>,
+    implementations: Vec<Implementation>,
+  },
+  Static {
+    name: String,
+    path: String,
+    signature: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+  },
+  TraitAlias {
+    name: String,
+    path: String,
+    signature: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+  },
+  Primitive {
+    name: String,
+    path: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    implementations: Vec<Implementation>,
+  },
+  Keyword {
+    name: String,
+    path: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+  },
+  AttributeMacro {
+    name: String,
+    path: String,
+    signature: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+  },
+  DeriveMacro {
+    name: String,
+    path: String,
+    signature: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    helper_attributes: Vec<String>,
+  },
+  ForeignType {
+    name: String,
+    path: String,
+    signature: String,
+    description: Option<String>,
+    stability: Stability,
+    examples: Vec<Example>,
+    links: Vec<String>,
+    aliases: Vec<String>,
+    source: Option<String>,
+    implementations: Vec<Implementation>,
   },
 }
 
 impl Item {
+  pub fn kind(&self) -> ItemKind {
+    match self {
+      Self::Function { .. } => ItemKind::Function,
+      Self::Struct { .. } => ItemKind::Struct,
+      Self::Enum { .. } => ItemKind::Enum,
+      Self::Trait { .. } => ItemKind::Trait,
+      Self::Macro { .. } => ItemKind::Macro,
+      Self::Type { .. } => ItemKind::Type,
+      Self::Constant { .. } => ItemKind::Constant,
+      Self::Module { .. } => ItemKind::Module,
+      Self::Union { .. } => ItemKind::Union,
+      Self::Static { .. } => ItemKind::Static,
+      Self::TraitAlias { .. } => ItemKind::TraitAlias,
+      Self::Primitive { .. } => ItemKind::Primitive,
+      Self::Keyword { .. } => ItemKind::Keyword,
+      Self::AttributeMacro { .. } => ItemKind::AttributeMacro,
+      Self::DeriveMacro { .. } => ItemKind::DeriveMacro,
+      Self::ForeignType { .. } => ItemKind::ForeignType,
+    }
+  }
+
+  pub fn implementations_mut(&mut self) -> Option<&mut Vec<Implementation>> {
+    match self {
+      Self::Struct {
+        implementations, ..
+      }
+      | Self::Enum {
+        implementations, ..
+      }
+      | Self::Union {
+        implementations, ..
+      }
+      | Self::Primitive {
+        implementations, ..
+      }
+      | Self::ForeignType {
+        implementations, ..
+      } => Some(implementations),
+      _ => None,
+    }
+  }
+
+  pub fn stability(&self) -> &Stability {
+    match self {
+      Self::Function { stability, .. }
+      | Self::Struct { stability, .. }
+      | Self::Enum { stability, .. }
+      | Self::Trait { stability, .. }
+      | Self::Macro { stability, .. }
+      | Self::Type { stability, .. }
+      | Self::Constant { stability, .. }
+      | Self::Module { stability, .. }
+      | Self::Union { stability, .. }
+      | Self::Static { stability, .. }
+      | Self::TraitAlias { stability, .. }
+      | Self::Primitive { stability, .. }
+      | Self::Keyword { stability, .. }
+      | Self::AttributeMacro { stability, .. }
+      | Self::DeriveMacro { stability, .. }
+      | Self::ForeignType { stability, .. } => stability,
+    }
+  }
+
+  /// Rewrite this item's description and those of everything nested in it,
+  /// such as fields, variants, methods and associated items.
+  pub fn map_descriptions(&mut self, f: &impl Fn(&str) -> String) {
+    self.map_own_descriptions(f);
+
+    for method in self.methods_mut() {
+      method.map_descriptions(f);
+    }
+  }
+
+  /// Rewrite the links in this item's docs into item paths with `resolve`,
+  /// and replace `links` (the raw references found in its signature) with
+  /// every path the item refers to. Nested methods collect their own links.
+  pub fn resolve_links(&mut self, resolve: &impl Fn(&str) -> Option<String>) {
+    let targets = RefCell::new(Vec::new());
+
+    self.map_own_descriptions(&|text| {
+      let (text, found) = resolve_links(text, resolve);
+      targets.borrow_mut().extend(found);
+      text
+    });
+
+    for method in self.methods_mut() {
+      method.resolve_links(resolve);
+    }
+
+    let path = self.path().to_string();
+
+    let links = self.links_mut();
+
+    let mut resolved = links
+      .iter()
+      .filter_map(|href| resolve(href))
+      .chain(targets.into_inner())
+      .filter(|link| *link != path)
+      .collect::<Vec<_>>();
+
+    resolved.sort();
+    resolved.dedup();
+
+    *links = resolved;
+  }
+
+  pub fn aliases(&self) -> &[String] {
+    match self {
+      Self::Function { aliases, .. }
+      | Self::Struct { aliases, .. }
+      | Self::Enum { aliases, .. }
+      | Self::Trait { aliases, .. }
+      | Self::Macro { aliases, .. }
+      | Self::Type { aliases, .. }
+      | Self::Constant { aliases, .. }
+      | Self::Module { aliases, .. }
+      | Self::Union { aliases, .. }
+      | Self::Static { aliases, .. }
+      | Self::TraitAlias { aliases, .. }
+      | Self::Primitive { aliases, .. }
+      | Self::Keyword { aliases, .. }
+      | Self::AttributeMacro { aliases, .. }
+      | Self::DeriveMacro { aliases, .. }
+      | Self::ForeignType { aliases, .. } => aliases,
+    }
+  }
+
+  pub fn aliases_mut(&mut self) -> &mut Vec<String> {
+    match self {
+      Self::Function { aliases, .. }
+      | Self::Struct { aliases, .. }
+      | Self::Enum { aliases, .. }
+      | Self::Trait { aliases, .. }
+      | Self::Macro { aliases, .. }
+      | Self::Type { aliases, .. }
+      | Self::Constant { aliases, .. }
+      | Self::Module { aliases, .. }
+      | Self::Union { aliases, .. }
+      | Self::Static { aliases, .. }
+      | Self::TraitAlias { aliases, .. }
+      | Self::Primitive { aliases, .. }
+      | Self::Keyword { aliases, .. }
+      | Self::AttributeMacro { aliases, .. }
+      | Self::DeriveMacro { aliases, .. }
+      | Self::ForeignType { aliases, .. } => aliases,
+    }
+  }
+
+  pub fn source(&self) -> Option<&str> {
+    match self {
+      Self::Function { source, .. }
+      | Self::Struct { source, .. }
+      | Self::Enum { source, .. }
+      | Self::Trait { source, .. }
+      | Self::Macro { source, .. }
+      | Self::Type { source, .. }
+      | Self::Constant { source, .. }
+      | Self::Module { source, .. }
+      | Self::Union { source, .. }
+      | Self::Static { source, .. }
+      | Self::TraitAlias { source, .. }
+      | Self::Primitive { source, .. }
+      | Self::Keyword { source, .. }
+      | Self::AttributeMacro { source, .. }
+      | Self::DeriveMacro { source, .. }
+      | Self::ForeignType { source, .. } => source.as_deref(),
+    }
+  }
+
+  fn links_mut(&mut self) -> &mut Vec<String> {
+    match self {
+      Self::Function { links, .. }
+      | Self::Struct { links, .. }
+      | Self::Enum { links, .. }
+      | Self::Trait { links, .. }
+      | Self::Macro { links, .. }
+      | Self::Type { links, .. }
+      | Self::Constant { links, .. }
+      | Self::Module { links, .. }
+      | Self::Union { links, .. }
+      | Self::Static { links, .. }
+      | Self::TraitAlias { links, .. }
+      | Self::Primitive { links, .. }
+      | Self::Keyword { links, .. }
+      | Self::AttributeMacro { links, .. }
+      | Self::DeriveMacro { links, .. }
+      | Self::ForeignType { links, .. } => links,
+    }
+  }
+
+  fn methods_mut(&mut self) -> Vec<&mut Item> {
+    match self {
+      Self::Trait {
+        required_methods,
+        provided_methods,
+        ..
+      } => required_methods
+        .iter_mut()
+        .chain(provided_methods)
+        .collect(),
+      _ => self
+        .implementations_mut()
+        .into_iter()
+        .flatten()
+        .flat_map(|implementation| &mut implementation.methods)
+        .collect(),
+    }
+  }
+
+  fn map_own_descriptions(&mut self, f: &impl Fn(&str) -> String) {
+    let apply = |description: &mut Option<String>| {
+      if let Some(text) = description {
+        *text = f(text);
+      }
+    };
+
+    match self {
+      Self::Function { description, .. }
+      | Self::Struct { description, .. }
+      | Self::Enum { description, .. }
+      | Self::Trait { description, .. }
+      | Self::Macro { description, .. }
+      | Self::Type { description, .. }
+      | Self::Constant { description, .. }
+      | Self::Module { description, .. }
+      | Self::Union { description, .. }
+      | Self::Static { description, .. }
+      | Self::TraitAlias { description, .. }
+      | Self::Primitive { description, .. }
+      | Self::Keyword { description, .. }
+      | Self::AttributeMacro { description, .. }
+      | Self::DeriveMacro { description, .. }
+      | Self::ForeignType { description, .. } => apply(description),
+    }
+
+    match self {
+      Self::Struct { fields, .. } | Self::Union { fields, .. } => {
+        fields
+          .iter_mut()
+          .for_each(|field| apply(&mut field.description));
+      }
+      Self::Enum { variants, .. } => {
+        for variant in variants {
+          apply(&mut variant.description);
+
+          if let VariantKind::Struct(fields) = &mut variant.kind {
+            fields
+              .iter_mut()
+              .for_each(|field| apply(&mut field.description));
+          }
+        }
+      }
+      Self::Trait {
+        associated_types,
+        associated_constants,
+        ..
+      } => {
+        associated_types
+          .iter_mut()
+          .for_each(|associated| apply(&mut associated.description));
+
+        associated_constants
+          .iter_mut()
+          .for_each(|associated| apply(&mut associated.description));
+      }
+      _ => {}
+    }
+  }
+
+  /// Examples on this item and on the methods nested inside it, each paired
+  /// with the path of the item it documents.
+  pub fn nested_examples(&self) -> Vec<(&str, &Example)> {
+    let mut examples = match self {
+      Self::Function { path, examples, .. }
+      | Self::Struct { path, examples, .. }
+      | Self::Enum { path, examples, .. }
+      | Self::Trait { path, examples, .. }
+      | Self::Macro { path, examples, .. }
+      | Self::Type { path, examples, .. }
+      | Self::Constant { path, examples, .. }
+      | Self::Module { path, examples, .. }
+      | Self::Union { path, examples, .. }
+      | Self::Static { path, examples, .. }
+      | Self::TraitAlias { path, examples, .. }
+      | Self::Primitive { path, examples, .. }
+      | Self::Keyword { path, examples, .. }
+      | Self::AttributeMacro { path, examples, .. }
+      | Self::DeriveMacro { path, examples, .. }
+      | Self::ForeignType { path, examples, .. } => examples
+        .iter()
+        .map(|example| (path.as_str(), example))
+        .collect::<Vec<_>>(),
+    };
+
+    examples.extend(self.methods().into_iter().flat_map(Item::nested_examples));
+
+    examples
+  }
+
+  /// This item or the method nested inside it that is documented at `path`,
+  /// either under its own path or one of its aliases.
+  pub fn find(&self, path: &str) -> Option<&Item> {
+    if self.path() == path || self.aliases().iter().any(|alias| alias == path) {
+      return Some(self);
+    }
+
+    self
+      .methods()
+      .into_iter()
+      .find_map(|method| method.find(path))
+  }
+
+  pub fn implementations(&self) -> &[Implementation] {
+    match self {
+      Self::Struct {
+        implementations, ..
+      }
+      | Self::Enum {
+        implementations, ..
+      }
+      | Self::Union {
+        implementations, ..
+      }
+      | Self::Primitive {
+        implementations, ..
+      }
+      | Self::ForeignType {
+        implementations, ..
+      } => implementations,
+      _ => &[],
+    }
+  }
+
+  fn methods(&self) -> Vec<&Item> {
+    match self {
+      Self::Trait {
+        required_methods,
+        provided_methods,
+        ..
+      } => required_methods.iter().chain(provided_methods).collect(),
+      _ => self
+        .implementations()
+        .iter()
+        .flat_map(|implementation| &implementation.methods)
+        .collect(),
+    }
+  }
+
+  pub fn path(&self) -> &str {
+    match self {
+      Self::Function { path, .. }
+      | Self::Struct { path, .. }
+      | Self::Enum { path, .. }
+      | Self::Trait { path, .. }
+      | Self::Macro { path, .. }
+      | Self::Type { path, .. }
+      | Self::Constant { path, .. }
+      | Self::Module { path, .. }
+      | Self::Union { path, .. }
+      | Self::Static { path, .. }
+      | Self::TraitAlias { path, .. }
+      | Self::Primitive { path, .. }
+      | Self::Keyword { path, .. }
+      | Self::AttributeMacro { path, .. }
+      | Self::DeriveMacro { path, .. }
+      | Self::ForeignType { path, .. } => path,
+    }
+  }
+
+  /// The item's declaration. Modules, primitives and keywords have none.
+  pub fn signature(&self) -> Option<&str> {
+    match self {
+      Self::Function { signature, .. }
+      | Self::Struct { signature, .. }
+      | Self::Enum { signature, .. }
+      | Self::Trait { signature, .. }
+      | Self::Macro { signature, .. }
+      | Self::Type { signature, .. }
+      | Self::Constant { signature, .. }
+      | Self::Union { signature, .. }
+      | Self::Static { signature, .. }
+      | Self::TraitAlias { signature, .. }
+      | Self::AttributeMacro { signature, .. }
+      | Self::DeriveMacro { signature, .. }
+      | Self::ForeignType { signature, .. } => Some(signature),
+      Self::Module { .. } | Self::Primitive { .. } | Self::Keyword { .. } => {
+        None
+      }
+    }
+  }
+
   pub fn search_items(&self) -> (&String, &Option<String>) {
     match self {
       Self::Function {
         name, description, ..
-      } => (name, description),
-      Self::Struct {
+      }
+      | Self::Struct {
         name, description, ..
-      } => (name, description),
-      Self::Enum {
+      }
+      | Self::Enum {
         name, description, ..
-      } => (name, description),
-      Self::Trait {
+      }
+      | Self::Trait {
         name, description, ..
-      } => (name, description),
-      Self::Macro {
+      }
+      | Self::Macro {
         name, description, ..
-      } => (name, description),
-      Self::Type {
+      }
+      | Self::Type {
         name, description, ..
-      } => (name, description),
-      Self::Constant {
+      }
+      | Self::Constant {
         name, description, ..
-      } => (name, description),
-      Self::Module {
+      }
+      | Self::Module {
+        name, description, ..
+      }
+      | Self::Union {
+        name, description, ..
+      }
+      | Self::Static {
+        name, description, ..
+      }
+      | Self::TraitAlias {
+        name, description, ..
+      }
+      | Self::Primitive {
+        name, description, ..
+      }
+      | Self::Keyword {
+        name, description, ..
+      }
+      | Self::AttributeMacro {
+        name, description, ..
+      }
+      | Self::DeriveMacro {
+        name, description, ..
+      }
+      | Self::ForeignType {
         name, description, ..
       } => (name, description),
     }
@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum ItemKind {
   Function,
   Struct,
@@ -10,19 +10,91 @@ pub enum ItemKind {
   Type,
   Constant,
   Module,
+  Union,
+  Static,
+  TraitAlias,
+  Primitive,
+  Keyword,
+  AttributeMacro,
+  DeriveMacro,
+  ForeignType,
 }
 
-impl From<&str> for ItemKind {
-  fn from(value: &str) -> Self {
-    match value {
-      v if v.starts_with("fn.") => Self::Function,
-      v if v.starts_with("struct.") => Self::Struct,
-      v if v.starts_with("enum.") => Self::Enum,
-      v if v.starts_with("trait.") => Self::Trait,
-      v if v.starts_with("macro.") => Self::Macro,
-      v if v.starts_with("type.") => Self::Type,
-      v if v.starts_with("constant.") => Self::Constant,
-      _ => Self::Module,
+impl ItemKind {
+  const ALL: [Self; 16] = [
+    Self::Function,
+    Self::Struct,
+    Self::Enum,
+    Self::Trait,
+    Self::Macro,
+    Self::Type,
+    Self::Constant,
+    Self::Module,
+    Self::Union,
+    Self::Static,
+    Self::TraitAlias,
+    Self::Primitive,
+    Self::Keyword,
+    Self::AttributeMacro,
+    Self::DeriveMacro,
+    Self::ForeignType,
+  ];
+
+  /// Determine the kind of item a rustdoc page documents from its file name,
+  /// e.g. `struct.Foo.html` or a module's `index.html`.
+  pub fn from_file_name(file_name: &str) -> Option<Self> {
+    if file_name == "index.html" {
+      return Some(Self::Module);
+    }
+
+    let (prefix, _) = file_name.split_once('.')?;
+
+    Self::ALL
+      .into_iter()
+      .find(|kind| kind.prefix() == Some(prefix))
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Function => "function",
+      Self::Struct => "struct",
+      Self::Enum => "enum",
+      Self::Trait => "trait",
+      Self::Macro => "macro",
+      Self::Type => "type",
+      Self::Constant => "constant",
+      Self::Module => "module",
+      Self::Union => "union",
+      Self::Static => "static",
+      Self::TraitAlias => "trait_alias",
+      Self::Primitive => "primitive",
+      Self::Keyword => "keyword",
+      Self::AttributeMacro => "attribute_macro",
+      Self::DeriveMacro => "derive_macro",
+      Self::ForeignType => "foreign_type",
     }
   }
+
+  /// The file name prefix rustdoc uses for pages of this kind. Modules are
+  /// documented by an `index.html` inside their own directory instead.
+  pub fn prefix(self) -> Option<&'static str> {
+    Some(match self {
+      Self::Function => "fn",
+      Self::Struct => "struct",
+      Self::Enum => "enum",
+      Self::Trait => "trait",
+      Self::Macro => "macro",
+      Self::Type => "type",
+      Self::Constant => "constant",
+      Self::Module => return None,
+      Self::Union => "union",
+      Self::Static => "static",
+      Self::TraitAlias => "traitalias",
+      Self::Primitive => "primitive",
+      Self::Keyword => "keyword",
+      Self::AttributeMacro => "attr",
+      Self::DeriveMacro => "derive",
+      Self::ForeignType => "foreigntype",
+    })
+  }
 }
@@ -0,0 +1,17 @@
+/// A CSS selector parsed once, the first time it is used.
+macro_rules! selector {
+  ($selector:expr $(,)?) => {{
+    static SELECTOR: std::sync::LazyLock<scraper::Selector> =
+      std::sync::LazyLock::new(|| scraper::Selector::parse($selector).unwrap());
+    &*SELECTOR
+  }};
+}
+
+/// A regex compiled once, the first time it is used.
+macro_rules! regex {
+  ($regex:expr $(,)?) => {{
+    static REGEX: std::sync::LazyLock<regex::Regex> =
+      std::sync::LazyLock::new(|| regex::Regex::new($regex).unwrap());
+    &*REGEX
+  }};
+}
@@ -1,47 +1,148 @@
 use {
   anyhow::anyhow,
+  api_change::ApiChange,
+  api_diff::{diff_docs, save_snapshot},
   arguments::Arguments,
+  associated_constant::AssociatedConstant,
+  associated_type::AssociatedType,
+  cache::{CACHE_DIRECTORY, Cache, CrateIndex},
+  cfg::{attribute_cfg, cfg_features, portability_cfg},
   clap::Parser,
+  crate_diff::CrateDiff,
+  declaration::{
+    closing, generic_parameters, impl_generics, normalize, split_bound,
+    split_generics, split_impl_header, split_top_level, split_where_clause,
+    where_predicates,
+  },
   documentation::Documentation,
+  entry::Entry,
   error::Error,
+  example::{Example, ExampleMatch},
+  field::Field,
+  function_signature::FunctionSignature,
+  generic_parameter::{GenericKind, GenericParameter},
+  implementation::{Implementation, ImplementationKind},
+  implementor::Implementor,
   item::Item,
   item_kind::ItemKind,
-  parser::{list_crates, lookup_crate},
+  markdown::{
+    code_language, html_to_markdown, markdown_examples, markdown_to_text,
+    resolve_links,
+  },
+  module_summary::ModuleSummary,
+  options::Options,
+  parameter::Parameter,
+  parser::{
+    changed_crates, crate_api, crate_doc_path, crate_lookups, crate_overview,
+    extract_implementors, format_crate_targets, get_source, html_to_text,
+    implementor, list_crate_targets, list_crates, list_targets, lookup_crate,
+    search_examples,
+  },
+  rayon::prelude::*,
+  receiver::Receiver,
+  reexport::ReExport,
   regex::Regex,
   rmcp::{
-    ErrorData as McpError, ServerHandler, ServiceExt,
+    ErrorData as McpError, Peer, RoleServer, ServerHandler, ServiceExt,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{CallToolResult, Content, ServerCapabilities, ServerInfo},
+    model::{
+      AnnotateAble, CallToolResult, Content, ListResourcesResult, LoggingLevel,
+      LoggingMessageNotificationParam, PaginatedRequestParam, RawResource,
+      ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+      ServerCapabilities, ServerInfo, SetLevelRequestParam,
+    },
     schemars::{self, JsonSchema},
+    service::RequestContext,
     tool, tool_handler, tool_router,
     transport::io::stdio,
   },
-  router::{LookupCrateRequest, Router},
-  scraper::{Html, Selector},
+  router::{
+    CrateOverviewRequest, DiffDocsRequest, GetSourceRequest,
+    LookupCrateRequest, Router, SaveSnapshotRequest, SearchByTypeRequest,
+    SearchExamplesRequest,
+  },
+  rustdoc_json::{crate_version, parse_json_file},
+  scraper::{ElementRef, Html, Selector},
+  search_index::{SearchIndex, load_search_index},
+  semver_impact::SemverImpact,
   serde::{Deserialize, Serialize},
+  snapshot::{SNAPSHOT_DIRECTORY, Snapshot},
+  source_code::SourceCode,
+  stability::{Deprecation, Stability},
   std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Display, Formatter},
     fs,
     io::stderr,
-    path::{Path, PathBuf},
+    iter, mem,
+    ops::Range,
+    path::{Component, Path, PathBuf},
     process::{self, Command},
+    sync::{Arc, Mutex},
   },
   subcommand::Subcommand,
-  tracing::{error, info},
+  tokio::task,
+  tracing::{error, info, warn},
   tracing_subscriber::{self, EnvFilter},
+  trait_implementors::find_implementors,
+  type_match::TypeMatch,
+  type_search::search_by_type,
+  variant::{Variant, VariantKind},
+  watcher::watch,
+  where_predicate::WherePredicate,
 };
 
+#[macro_use]
+mod macros;
+
+mod api_change;
+mod api_diff;
 mod arguments;
+mod associated_constant;
+mod associated_type;
+mod cache;
+mod cfg;
+mod crate_diff;
+mod declaration;
 mod documentation;
+mod entry;
 mod error;
+mod example;
+mod field;
+mod function_signature;
+mod generic_parameter;
+mod implementation;
+mod implementor;
 mod item;
 mod item_kind;
+mod markdown;
+mod module_summary;
+mod options;
+mod parameter;
 mod parser;
+mod receiver;
+mod reexport;
 mod router;
+mod rustdoc_json;
+mod search_index;
+mod semver_impact;
+mod snapshot;
+mod source_code;
+mod stability;
 mod subcommand;
+mod trait_implementors;
+mod type_match;
+mod type_search;
+mod variant;
+mod watcher;
+mod where_predicate;
 
 const DOC_PATH: &str = "target/doc";
 
+/// The crates whose docs ship with the toolchain's `rust-docs` component.
+const STD_CRATES: [&str; 5] = ["alloc", "core", "proc_macro", "std", "test"];
+
 type Result<T = (), E = Error> = std::result::Result<T, E>;
 
 #[tokio::main]
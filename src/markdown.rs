@@ -0,0 +1,423 @@
+use {
+  super::*,
+  scraper::{Node, node::Element},
+};
+
+/// Convert a rendered docblock back into Markdown, keeping code blocks,
+/// lists, headings, tables, emphasis and links intact.
+pub fn html_to_markdown(html: &str) -> String {
+  let fragment = Html::parse_fragment(html);
+
+  let markdown = render_children(fragment.root_element());
+
+  let mut lines = Vec::new();
+
+  let mut fenced = false;
+
+  for line in markdown.lines() {
+    if line.trim_start().starts_with("```") {
+      fenced = !fenced;
+    }
+
+    let line = if fenced { line } else { line.trim_end() };
+
+    if !fenced
+      && line.is_empty()
+      && lines.last().is_none_or(|last: &&str| last.is_empty())
+    {
+      continue;
+    }
+
+    lines.push(line);
+  }
+
+  lines.join("\n").trim().to_string()
+}
+
+/// Flatten Markdown into a single line of text for compact output.
+pub fn markdown_to_text(markdown: &str) -> String {
+  let text = markdown
+    .lines()
+    .filter(|line| !line.trim_start().starts_with("```"))
+    .map(|line| line.trim_start().trim_start_matches('#'))
+    .collect::<Vec<_>>()
+    .join(" ");
+
+  let text = regex!(r"!?\[([^\]]*)\]\([^)]*\)")
+    .replace_all(&text, "$1")
+    .replace("**", "")
+    .replace('`', "");
+
+  text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Point the links in Markdown at the item paths `resolve` maps their
+/// destinations to, returning the rewritten text along with every path found.
+/// Inline (`[text](dest)`), reference (`[text][dest]`), shortcut (`[dest]`)
+/// links and link definitions are handled; links inside code are left alone.
+pub fn resolve_links(
+  markdown: &str,
+  resolve: impl Fn(&str) -> Option<String>,
+) -> (String, Vec<String>) {
+  let link = regex!(
+    r"(!?)\[([^\[\]]*)\](?:\(([^()\s]*)\)|\[([^\[\]]*)\]|(:[ \t]*)(\S+))?"
+  );
+
+  let mut targets = Vec::new();
+
+  let mut fenced = false;
+
+  let lines = markdown
+    .lines()
+    .map(|line| {
+      let trimmed = line.trim_start();
+
+      if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        fenced = !fenced;
+      }
+
+      if fenced {
+        return line.to_string();
+      }
+
+      let code = code_spans(line);
+
+      link
+        .replace_all(line, |captures: &regex::Captures| {
+          let whole = captures.get(0).unwrap();
+
+          let text = &captures[2];
+
+          let inside_code =
+            code.iter().any(|span| span.contains(&whole.start()));
+
+          let definition = captures.get(5).is_some()
+            && line[..whole.start()].trim().is_empty();
+
+          let destination = match (captures.get(3), captures.get(4)) {
+            (Some(destination), _) => destination.as_str(),
+            (_, Some(reference)) if !reference.as_str().is_empty() => {
+              reference.as_str()
+            }
+            _ if definition => &captures[6],
+            _ => text,
+          };
+
+          let resolved = (!inside_code && captures[1].is_empty())
+            .then(|| resolve(destination))
+            .flatten();
+
+          match resolved {
+            Some(path) => {
+              let rewritten = if definition {
+                format!("[{text}]{}{path}", &captures[5])
+              } else {
+                let rest = captures
+                  .get(5)
+                  .map(|_| format!("{}{}", &captures[5], &captures[6]))
+                  .unwrap_or_default();
+
+                format!("[{text}]({path}){rest}")
+              };
+
+              targets.push(path);
+
+              rewritten
+            }
+            None => whole.as_str().to_string(),
+          }
+        })
+        .into_owned()
+    })
+    .collect::<Vec<_>>();
+
+  (lines.join("\n"), targets)
+}
+
+/// Byte ranges of the inline code spans in a line of Markdown.
+fn code_spans(line: &str) -> Vec<Range<usize>> {
+  let ticks = line.match_indices('`').map(|(index, _)| index);
+
+  ticks
+    .clone()
+    .step_by(2)
+    .zip(ticks.skip(1).step_by(2))
+    .map(|(start, end)| start..end)
+    .collect()
+}
+
+/// Fenced code blocks in Markdown docs, as found in rustdoc JSON. Untagged
+/// blocks and blocks carrying only test attributes are Rust, whose hidden
+/// `# ` lines are dropped the same way rustdoc hides them.
+pub fn markdown_examples(markdown: &str) -> Vec<Example> {
+  let mut examples = Vec::new();
+
+  let mut heading = None;
+
+  let mut fence: Option<(String, Vec<String>, Vec<&str>)> = None;
+
+  for line in markdown.lines() {
+    let trimmed = line.trim_start();
+
+    match &mut fence {
+      Some((marker, _, lines)) => {
+        let closing = trimmed.trim_end();
+
+        if closing.len() >= marker.len()
+          && closing.chars().all(|c| marker.starts_with(c))
+        {
+          let (_, info, lines) = fence.take().unwrap();
+          examples.push(example(heading.clone(), &info, &lines));
+        } else {
+          lines.push(line);
+        }
+      }
+      None => {
+        if let Some(marker) = ["```", "~~~"]
+          .into_iter()
+          .find(|marker| trimmed.starts_with(marker))
+        {
+          let length = trimmed.len()
+            - trimmed
+              .trim_start_matches(marker.chars().next().unwrap())
+              .len();
+
+          fence = Some((
+            trimmed[..length].to_string(),
+            trimmed[length..]
+              .split(|c: char| c == ',' || c.is_whitespace())
+              .filter(|token| !token.is_empty())
+              .map(str::to_string)
+              .collect(),
+            Vec::new(),
+          ));
+        } else if trimmed.starts_with('#') {
+          heading = Some(trimmed.trim_start_matches('#').trim().to_string())
+            .filter(|heading| !heading.is_empty());
+        }
+      }
+    }
+  }
+
+  examples
+}
+
+fn example(
+  heading: Option<String>,
+  info: &[String],
+  lines: &[&str],
+) -> Example {
+  let is_attribute = |token: &str| {
+    matches!(
+      token,
+      "ignore"
+        | "no_run"
+        | "should_panic"
+        | "compile_fail"
+        | "test_harness"
+        | "standalone_crate"
+        | "allow_fail"
+    ) || token.starts_with("edition")
+      || token.starts_with("ignore-")
+  };
+
+  let language = info
+    .iter()
+    .find(|token| !is_attribute(token))
+    .cloned()
+    .unwrap_or_else(|| "rust".to_string());
+
+  let code = if language == "rust" {
+    lines
+      .iter()
+      .filter(|line| {
+        let line = line.trim_start();
+        line != "#" && !line.starts_with("# ")
+      })
+      .map(|line| {
+        let indent = line.len() - line.trim_start().len();
+
+        match line[indent..].strip_prefix("##") {
+          Some(rest) => format!("{}#{rest}", &line[..indent]),
+          None => line.to_string(),
+        }
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  } else {
+    lines.join("\n")
+  };
+
+  Example {
+    heading,
+    language,
+    attributes: info
+      .iter()
+      .filter(|token| is_attribute(token))
+      .cloned()
+      .collect(),
+    code,
+  }
+}
+
+fn render_children(element: ElementRef) -> String {
+  element
+    .children()
+    .map(|node| match node.value() {
+      Node::Text(text) => regex!(r"\s+").replace_all(text, " ").into_owned(),
+      Node::Element(_) => ElementRef::wrap(node)
+        .map(render_element)
+        .unwrap_or_default(),
+      _ => String::new(),
+    })
+    .collect()
+}
+
+fn render_element(element: ElementRef) -> String {
+  let value = element.value();
+
+  if has_class(value, &["doc-anchor", "anchor", "tooltip"]) {
+    return String::new();
+  }
+
+  let inline = || render_children(element).trim().to_string();
+
+  match value.name() {
+    "script" | "style" | "button" => String::new(),
+    "p" => format!("\n\n{}\n\n", inline()),
+    "br" => "\n".to_string(),
+    "hr" => "\n\n---\n\n".to_string(),
+    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => format!(
+      "\n\n{} {}\n\n",
+      "#".repeat(value.name()[1..].parse().unwrap_or(1)),
+      inline()
+    ),
+    "pre" => format!(
+      "\n\n```{}\n{}\n```\n\n",
+      code_language(element),
+      element.text().collect::<String>().trim_end()
+    ),
+    "code" => format!("`{}`", element.text().collect::<String>()),
+    "strong" | "b" => wrap_inline(&inline(), "**"),
+    "em" | "i" => wrap_inline(&inline(), "*"),
+    "del" | "s" => wrap_inline(&inline(), "~~"),
+    "a" => match (value.attr("href"), inline()) {
+      (_, text) if text.is_empty() => String::new(),
+      (Some(href), text) if !href.is_empty() => format!("[{text}]({href})"),
+      (_, text) => text,
+    },
+    "img" => format!(
+      "![{}]({})",
+      value.attr("alt").unwrap_or_default(),
+      value.attr("src").unwrap_or_default()
+    ),
+    "ul" | "ol" => render_list(element, value.name() == "ol"),
+    "blockquote" => format!(
+      "\n\n{}\n\n",
+      html_to_markdown(&element.inner_html())
+        .lines()
+        .map(|line| format!("> {line}").trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+    ),
+    "table" => render_table(element),
+    _ => render_children(element),
+  }
+}
+
+fn render_list(element: ElementRef, ordered: bool) -> String {
+  let items = element
+    .children()
+    .filter_map(ElementRef::wrap)
+    .filter(|child| child.value().name() == "li")
+    .enumerate()
+    .map(|(index, item)| {
+      let marker = if ordered {
+        format!("{}. ", index + 1)
+      } else {
+        "- ".to_string()
+      };
+
+      let content = html_to_markdown(&item.inner_html());
+
+      let indent = " ".repeat(marker.len());
+
+      format!(
+        "{marker}{}",
+        content
+          .lines()
+          .filter(|line| !line.is_empty())
+          .enumerate()
+          .map(|(index, line)| if index == 0 {
+            line.to_string()
+          } else {
+            format!("{indent}{line}")
+          })
+          .collect::<Vec<_>>()
+          .join("\n")
+      )
+    })
+    .collect::<Vec<_>>();
+
+  format!("\n\n{}\n\n", items.join("\n"))
+}
+
+fn render_table(element: ElementRef) -> String {
+  let rows = element
+    .select(selector!("tr"))
+    .map(|row| {
+      row
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+        .map(|cell| render_children(cell).trim().replace('|', "\\|"))
+        .collect::<Vec<_>>()
+    })
+    .filter(|cells| !cells.is_empty())
+    .collect::<Vec<_>>();
+
+  let Some(header) = rows.first() else {
+    return String::new();
+  };
+
+  let mut lines = vec![
+    format!("| {} |", header.join(" | ")),
+    format!("|{}", " --- |".repeat(header.len())),
+  ];
+
+  lines.extend(
+    rows
+      .iter()
+      .skip(1)
+      .map(|cells| format!("| {} |", cells.join(" | "))),
+  );
+
+  format!("\n\n{}\n\n", lines.join("\n"))
+}
+
+/// rustdoc marks Rust examples with a `rust` class on the `pre`, and other
+/// fenced blocks with a `language-*` class.
+pub fn code_language(element: ElementRef) -> String {
+  element
+    .descendants()
+    .filter_map(ElementRef::wrap)
+    .flat_map(|element| element.value().classes())
+    .find_map(|class| match class {
+      "rust" => Some("rust"),
+      _ => class.strip_prefix("language-"),
+    })
+    .unwrap_or_default()
+    .to_string()
+}
+
+fn has_class(element: &Element, classes: &[&str]) -> bool {
+  element.classes().any(|class| classes.contains(&class))
+}
+
+fn wrap_inline(text: &str, marker: &str) -> String {
+  if text.is_empty() {
+    String::new()
+  } else {
+    format!("{marker}{text}{marker}")
+  }
+}
@@ -0,0 +1,9 @@
+use super::*;
+
+/// A top-level module of a crate along with the first line of its docs.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ModuleSummary {
+  pub name: String,
+  pub path: String,
+  pub summary: Option<String>,
+}
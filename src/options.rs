@@ -0,0 +1,311 @@
+use super::*;
+
+#[derive(Clone, Debug, Default, Parser)]
+pub struct Options {
+  #[clap(
+    long,
+    global = true,
+    env = "CRATES_MCP_DOC_PATH",
+    value_name = "PATH",
+    help = "Directory holding the generated documentation [default: discovered through cargo metadata]"
+  )]
+  doc_path: Option<String>,
+  #[clap(
+    long,
+    global = true,
+    value_name = "PATH",
+    help = "Path to the Cargo.toml of the crate or workspace to document"
+  )]
+  manifest_path: Option<PathBuf>,
+  #[clap(
+    long,
+    global = true,
+    help = "Disable the on-disk index cache under the documentation directory"
+  )]
+  pub no_cache: bool,
+  #[clap(
+    long,
+    global = true,
+    env = "CRATES_MCP_SNAPSHOT_DIR",
+    value_name = "PATH",
+    help = "Directory API snapshots are saved in [default: crates-mcp/snapshots under the user cache directory]"
+  )]
+  snapshot_dir: Option<PathBuf>,
+  #[clap(skip)]
+  std_doc_path: Option<String>,
+  #[clap(skip)]
+  workspace_root: Option<PathBuf>,
+}
+
+impl Options {
+  pub fn cache(&self) -> Cache {
+    Cache::new(self.doc_path(), !self.no_cache)
+  }
+
+  pub fn doc_path(&self) -> &str {
+    self.doc_path.as_deref().unwrap_or(DOC_PATH)
+  }
+
+  /// The documentation directory for `crate_name`, which is the toolchain's
+  /// for standard library crates.
+  pub fn doc_path_for(&self, crate_name: &str) -> &str {
+    crate_doc_path(crate_name, self.doc_path(), self.std_doc_path())
+  }
+
+  pub fn manifest_path(&self) -> Option<&Path> {
+    self.manifest_path.as_deref()
+  }
+
+  /// Where snapshots are saved, which is outside the target directory so that
+  /// `cargo clean` doesn't delete them, unless there is no user cache
+  /// directory to fall back to.
+  pub fn snapshot_dir(&self) -> PathBuf {
+    self.snapshot_dir.clone().unwrap_or_else(|| {
+      Path::new(self.doc_path())
+        .join(CACHE_DIRECTORY)
+        .join(SNAPSHOT_DIRECTORY)
+    })
+  }
+
+  pub fn std_doc_path(&self) -> Option<&str> {
+    self.std_doc_path.as_deref()
+  }
+
+  /// The workspace root found while discovering the documentation, which the
+  /// source spans in rustdoc JSON are relative to.
+  pub fn workspace_root(&self) -> Option<&Path> {
+    self.workspace_root.as_deref()
+  }
+
+  /// Settle on a documentation directory once, up front. An explicit path
+  /// wins, then the target directory `cargo metadata` reports, which accounts
+  /// for `CARGO_TARGET_DIR`, `build.target-dir` and shared workspace targets,
+  /// and finally the nearest `target/doc` above the working directory.
+  pub fn resolve(mut self) -> Self {
+    if self.doc_path.is_none() {
+      let (doc_path, workspace_root) = self
+        .metadata_doc_path()
+        .or_else(|| self.ancestor_doc_path())
+        .unzip();
+
+      self.doc_path = doc_path.map(|path| path.to_string_lossy().into_owned());
+      self.workspace_root = workspace_root;
+    }
+
+    info!("reading documentation from {}", self.doc_path());
+
+    if self.snapshot_dir.is_none() {
+      self.snapshot_dir = user_cache_dir()
+        .map(|directory| directory.join("crates-mcp").join(SNAPSHOT_DIRECTORY));
+    }
+
+    if self.snapshot_dir.is_none() {
+      warn!(
+        "no user cache directory found, saving snapshots under {}, where \
+         `cargo clean` deletes them",
+        self.snapshot_dir().display()
+      );
+    }
+
+    self.std_doc_path = sysroot_doc_path()
+      .map(|path| path.to_string_lossy().into_owned())
+      .inspect(|path| {
+        info!("reading standard library documentation from {path}")
+      });
+
+    self
+  }
+
+  /// The documentation directory under the target directory `cargo
+  /// metadata` reports, along with the workspace root.
+  fn metadata_doc_path(&self) -> Option<(PathBuf, PathBuf)> {
+    let mut command = Command::new("cargo");
+
+    command.args(["metadata", "--format-version", "1", "--no-deps"]);
+
+    if let Some(manifest_path) = &self.manifest_path {
+      command.arg("--manifest-path").arg(manifest_path);
+    }
+
+    let output = command
+      .output()
+      .inspect_err(|error| warn!("failed to run cargo metadata: {error}"))
+      .ok()
+      .filter(|output| output.status.success())?;
+
+    let metadata = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+      .inspect_err(|error| warn!("failed to parse cargo metadata: {error}"))
+      .ok()?;
+
+    Some((
+      Path::new(metadata["target_directory"].as_str()?).join("doc"),
+      PathBuf::from(metadata["workspace_root"].as_str()?),
+    ))
+  }
+
+  /// Walk up from the manifest's directory, or the working directory, to the
+  /// first directory that has documentation in `target/doc`, falling back to
+  /// the outermost workspace root found along the way. Returns the
+  /// documentation directory along with the directory it was found in.
+  fn ancestor_doc_path(&self) -> Option<(PathBuf, PathBuf)> {
+    let start = match &self.manifest_path {
+      Some(manifest_path) => fs::canonicalize(manifest_path)
+        .ok()?
+        .parent()?
+        .to_path_buf(),
+      None => std::env::current_dir().ok()?,
+    };
+
+    start
+      .ancestors()
+      .find(|directory| directory.join(DOC_PATH).is_dir())
+      .or_else(|| {
+        start
+          .ancestors()
+          .filter(|directory| {
+            fs::read_to_string(directory.join("Cargo.toml"))
+              .is_ok_and(|manifest| manifest.contains("[workspace]"))
+          })
+          .last()
+      })
+      .map(|directory| (directory.join(DOC_PATH), directory.to_path_buf()))
+  }
+}
+
+/// The platform's per-user cache directory, such as `~/.cache` on Linux.
+fn user_cache_dir() -> Option<PathBuf> {
+  let var = |name: &str| {
+    std::env::var_os(name)
+      .map(PathBuf::from)
+      .filter(|path| path.is_absolute())
+  };
+
+  if cfg!(windows) {
+    return var("LOCALAPPDATA");
+  }
+
+  if cfg!(target_os = "macos") {
+    return var("HOME").map(|home| home.join("Library/Caches"));
+  }
+
+  var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache")))
+}
+
+/// Where the `rust-docs` rustup component installs the standard library's
+/// documentation for the active toolchain.
+fn sysroot_doc_path() -> Option<PathBuf> {
+  let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+
+  let output = Command::new(rustc)
+    .args(["--print", "sysroot"])
+    .output()
+    .ok()
+    .filter(|output| output.status.success())?;
+
+  let path = Path::new(String::from_utf8(output.stdout).ok()?.trim())
+    .join("share/doc/rust/html");
+
+  path.is_dir().then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, tempfile::TempDir};
+
+  fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+  }
+
+  fn package(directory: &Path) -> PathBuf {
+    write(
+      &directory.join("Cargo.toml"),
+      "[package]\nname = \"probe\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+    );
+
+    write(&directory.join("src/lib.rs"), "");
+
+    directory.join("Cargo.toml")
+  }
+
+  #[test]
+  fn resolve_precedence() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let root = fs::canonicalize(temp_dir.path()).unwrap();
+
+    // The ancestor search would settle on `root/target/doc`, while cargo
+    // reports the package's own target directory.
+    fs::create_dir_all(root.join(DOC_PATH)).unwrap();
+
+    let member = root.join("member");
+
+    let manifest = package(&member);
+
+    let resolve = |arguments: &[&str]| {
+      let options = Options::try_parse_from(
+        ["crates-mcp", "--manifest-path", manifest.to_str().unwrap()]
+          .iter()
+          .chain(arguments),
+      )
+      .unwrap()
+      .resolve();
+
+      (
+        options.doc_path().to_string(),
+        options.workspace_root().map(Path::to_path_buf),
+      )
+    };
+
+    let metadata = member.join(DOC_PATH).to_string_lossy().into_owned();
+
+    assert_eq!(resolve(&[]), (metadata, Some(member.clone())));
+
+    // The environment variable sits between the two, which
+    // `tests/doc_path.rs` checks without touching this process's environment.
+    assert_eq!(
+      resolve(&["--doc-path", "/flag/doc"]),
+      ("/flag/doc".to_string(), None)
+    );
+  }
+
+  #[test]
+  fn ancestor_doc_path() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let root = fs::canonicalize(temp_dir.path()).unwrap();
+
+    write(
+      &root.join("Cargo.toml"),
+      "[workspace]\nmembers = [\"member\"]\n",
+    );
+
+    let member = root.join("member");
+
+    let options = Options {
+      manifest_path: Some(package(&member)),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      options.ancestor_doc_path(),
+      Some((root.join(DOC_PATH), root.clone()))
+    );
+
+    fs::create_dir_all(member.join(DOC_PATH)).unwrap();
+
+    assert_eq!(
+      options.ancestor_doc_path(),
+      Some((member.join(DOC_PATH), member.clone()))
+    );
+
+    fs::remove_dir_all(member.join("target")).unwrap();
+
+    fs::create_dir_all(root.join(DOC_PATH)).unwrap();
+
+    assert_eq!(
+      options.ancestor_doc_path(),
+      Some((root.join(DOC_PATH), root))
+    );
+  }
+}
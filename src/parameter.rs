@@ -0,0 +1,9 @@
+use super::*;
+
+/// A function parameter other than `self`, with its pattern as written.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Parameter {
+  pub name: String,
+  #[serde(rename = "type")]
+  pub ty: String,
+}
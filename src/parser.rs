@@ -1,5 +1,11 @@
 use super::*;
 
+/// How many pages are parsed in parallel before their items are filtered.
+const PARSE_BATCH_SIZE: usize = 512;
+
+/// The name used for the documentation tree built without `--target`.
+const HOST_TARGET: &str = "host";
+
 pub fn list_crates(path: &str) -> Result<Vec<String>> {
   let path = PathBuf::from(path);
 
@@ -12,808 +18,5880 @@ pub fn list_crates(path: &str) -> Result<Vec<String>> {
 
   let mut crates = fs::read_dir(&path)?
     .filter_map(|entry| entry.ok())
-    .filter(|entry| entry.path().is_dir())
     .filter_map(|entry| {
-      entry
-        .path()
-        .file_name()
+      let path = entry.path();
+
+      let name = if path.is_dir() {
+        path.file_name()
+      } else if path.extension().is_some_and(|ext| ext == "json") {
+        path.file_stem()
+      } else {
+        None
+      };
+
+      name
         .and_then(|name| name.to_str())
-        .filter(|name| *name != "src" && !name.contains('.'))
+        .filter(|name| is_crate_name(name))
         .map(|name| name.to_string())
     })
     .collect::<Vec<String>>();
 
   crates.sort();
+  crates.dedup();
 
   Ok(crates)
 }
 
-pub fn lookup_crate(
-  request: &LookupCrateRequest,
-  path: &str,
-) -> Result<Documentation> {
-  let path = PathBuf::from(path).join(request.name.clone());
-
-  if !path.exists() {
-    return Err(Error(anyhow!(
-      "documentation not found for crate '{}' at {:?}",
-      request.name,
-      path
-    )));
-  }
+/// The crates whose documentation lives at any of `paths`, which are files
+/// that changed somewhere under the documentation directory `root`. Shared
+/// files such as `search-index.js`, `static.files/` and `src/` belong to no
+/// crate.
+pub fn changed_crates(root: &Path, paths: &[PathBuf]) -> BTreeSet<String> {
+  paths
+    .iter()
+    .filter_map(|path| path.strip_prefix(root).ok())
+    .filter_map(|relative| {
+      let mut components = relative.components();
+
+      let first = Path::new(components.next()?.as_os_str());
+
+      let name = if components.next().is_some() || first.extension().is_none() {
+        first.to_str()
+      } else if first.extension().is_some_and(|ext| ext == "json") {
+        first.file_stem().and_then(|stem| stem.to_str())
+      } else {
+        None
+      };
+
+      name.filter(|name| is_crate_name(name)).map(str::to_string)
+    })
+    .collect()
+}
 
-  let mut items = parse_directory(&path)?;
+/// Every crate under the documentation directory and under the per-target
+/// trees beside it, along with the targets each one is documented for. The
+/// standard library crates in the toolchain's docs at `std_path` count as
+/// documented for the host.
+pub fn list_crate_targets(
+  path: &str,
+  std_path: Option<&str>,
+) -> Result<BTreeMap<String, Vec<String>>> {
+  let targets = list_targets(Path::new(path));
 
-  if let Some(ref filter_type) = request.item_type {
-    items = filter_by_item_type(items, filter_type);
+  let std = std_path
+    .and_then(|std_path| list_crates(std_path).ok())
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|name| STD_CRATES.contains(&name.as_str()))
+    .collect::<Vec<_>>();
+
+  let mut crates = BTreeMap::<String, Vec<String>>::new();
+
+  let host = match list_crates(path) {
+    Ok(host) => host,
+    Err(error) if targets.is_empty() && std.is_empty() => return Err(error),
+    Err(_) => Vec::new(),
+  };
+
+  for name in host {
+    crates
+      .entry(name)
+      .or_default()
+      .push(HOST_TARGET.to_string());
   }
 
-  if let Some(ref search_query) = request.query {
-    items = filter_by_query(items, search_query);
+  for (target, root) in targets {
+    for name in list_crates(&root.to_string_lossy())? {
+      crates.entry(name).or_default().push(target.clone());
+    }
   }
 
-  let offset = request.offset.unwrap_or(0);
+  for name in std {
+    let targets = crates.entry(name).or_default();
 
-  if offset > 0 && offset < items.len() {
-    items = items.into_iter().skip(offset).collect();
-  } else if offset >= items.len() {
-    items = Vec::new();
+    if !targets.iter().any(|target| target == HOST_TARGET) {
+      targets.insert(0, HOST_TARGET.to_string());
+    }
   }
 
-  if let Some(limit) = request.limit {
-    items.truncate(limit);
+  Ok(crates)
+}
+
+/// The documentation directory for `name`. The standard library crates come
+/// from the toolchain's docs at `std_path`, unless `cargo doc` documented them
+/// too.
+pub fn crate_doc_path<'a>(
+  name: &str,
+  path: &'a str,
+  std_path: Option<&'a str>,
+) -> &'a str {
+  match std_path {
+    Some(std_path)
+      if STD_CRATES.contains(&name) && !documents(Path::new(path), name) =>
+    {
+      std_path
+    }
+    _ => path,
   }
+}
 
-  Ok(Documentation {
-    name: request.name.to_string(),
-    items,
+/// A lookup for the crate `name`, or for every crate `list_crate_targets`
+/// finds, each paired with the documentation directory to read it from. A
+/// crate documented for several targets is only read once, preferring the
+/// host tree.
+pub fn crate_lookups<'a>(
+  name: Option<&str>,
+  path: &'a str,
+  std_path: Option<&'a str>,
+) -> Result<Vec<(LookupCrateRequest, &'a str)>> {
+  let lookup = |name: String, target: Option<String>| {
+    let root = crate_doc_path(&name, path, std_path);
+
+    (
+      LookupCrateRequest {
+        name,
+        target,
+        ..Default::default()
+      },
+      root,
+    )
+  };
+
+  Ok(match name {
+    Some(name) => vec![lookup(name.to_string(), None)],
+    None => list_crate_targets(path, std_path)?
+      .into_iter()
+      .map(|(name, targets)| lookup(name, targets.into_iter().next()))
+      .collect(),
   })
 }
 
-fn parse_directory(dir: &Path) -> Result<Vec<Item>> {
-  let entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>();
-
-  entries?.into_iter().map(|entry| entry.path()).try_fold(
-    Vec::new(),
-    |mut acc, path| -> Result<Vec<Item>> {
-      match (
-        path.is_dir(),
-        path.extension().is_some_and(|ext| ext == "html"),
-      ) {
-        (true, _) => acc.extend(parse_directory(&path)?),
-        (false, true) => {
-          if let Some(item) = parse_html_file(&path)? {
-            acc.push(item);
-          }
-        }
-        _ => {}
+/// One line per crate, naming the targets it is documented for unless that is
+/// only the host, e.g. `app (host, wasm32-unknown-unknown)`.
+pub fn format_crate_targets(crates: &BTreeMap<String, Vec<String>>) -> String {
+  crates
+    .iter()
+    .map(|(name, targets)| {
+      if targets.iter().all(|target| target == HOST_TARGET) {
+        name.clone()
+      } else {
+        format!("{name} ({})", targets.join(", "))
       }
-      Ok(acc)
-    },
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// The per-target documentation trees rustdoc writes to `target/<triple>/doc`
+/// when building with `--target`, next to the host tree at `target/doc`.
+pub fn list_targets(path: &Path) -> Vec<(String, PathBuf)> {
+  let Some(target_directory) = path
+    .parent()
+    .filter(|_| path.file_name().is_some_and(|name| name == "doc"))
+  else {
+    return Vec::new();
+  };
+
+  let mut targets = fs::read_dir(target_directory)
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| entry.ok())
+    .filter_map(|entry| {
+      let root = entry.path().join("doc");
+
+      let name = entry.file_name().to_str()?.to_string();
+
+      (name != "doc" && root.is_dir()).then_some((name, root))
+    })
+    .collect::<Vec<_>>();
+
+  targets.sort();
+
+  targets
+}
+
+/// The documentation tree for `name`: the one built for `target` when given,
+/// otherwise the host tree, falling back to the first per-target tree that
+/// documents the crate. The tree's target triple is returned alongside it,
+/// or `None` for the host tree.
+fn locate_crate(
+  path: &str,
+  name: &str,
+  target: Option<&str>,
+) -> Result<(PathBuf, Option<String>)> {
+  let host = PathBuf::from(path);
+
+  if let Some(target) = target.filter(|target| *target != HOST_TARGET) {
+    return list_targets(&host)
+      .into_iter()
+      .find(|(triple, _)| triple == target)
+      .map(|(triple, root)| (root, Some(triple)))
+      .ok_or_else(|| {
+        Error(anyhow!(
+          "no documentation built for target '{target}' next to {host:?}"
+        ))
+      });
+  }
+
+  if target.is_some() || documents(&host, name) {
+    return Ok((host, None));
+  }
+
+  Ok(
+    list_targets(&host)
+      .into_iter()
+      .find(|(_, root)| documents(root, name))
+      .map_or((host, None), |(triple, root)| (root, Some(triple))),
   )
 }
 
-fn parse_html_file(file_path: &Path) -> Result<Option<Item>> {
-  let document = Html::parse_document(&fs::read_to_string(file_path)?);
+/// Whether the documentation tree at `root` has pages or JSON for `name`.
+fn documents(root: &Path, name: &str) -> bool {
+  root.join(name).is_dir() || root.join(format!("{name}.json")).is_file()
+}
 
-  let file_name = file_path
-    .file_name()
-    .and_then(|n| n.to_str())
-    .ok_or_else(|| anyhow!("invalid file name"))
-    .unwrap();
+fn is_crate_name(name: &str) -> bool {
+  name != "src" && !name.contains('.')
+}
+
+pub fn lookup_crate(
+  request: &LookupCrateRequest,
+  path: &str,
+  cache: &Cache,
+) -> Result<Documentation> {
+  let (root, target) =
+    locate_crate(path, &request.name, request.target.as_deref())?;
+
+  let json_path = root.join(format!("{}.json", request.name));
+
+  let path = root.join(&request.name);
+
+  // Each target's tree gets its own cache file, since the same crate is
+  // documented differently per target.
+  let key = match &target {
+    Some(target) => format!("{}.{target}", request.name),
+    None => request.name.clone(),
+  };
+
+  let mut index = cache.load(&key);
 
-  let signature_element = document
-    .select(&Selector::parse("pre.rust.item-decl").unwrap())
-    .next();
+  let mut items = if json_path.is_file() {
+    index.retain(&HashSet::from([json_path.as_path()]));
 
-  if let Some(element) = signature_element {
-    let signature = html_to_text(element.inner_html());
+    let mut items = index.items(&json_path, parse_json_file)?;
 
-    if signature.is_empty() {
-      return Ok(None);
+    if let Some(ref prefix) = request.path {
+      items.retain(|item| has_path_prefix(item.path(), prefix));
     }
 
-    let description = extract_description(&document);
+    if let Some(ref filter_type) = request.item_type {
+      items = filter_by_item_type(items, filter_type);
+    }
 
-    let name = extract_item_name(file_name).unwrap();
+    if let Some(ref search_query) = request.query {
+      items.retain(|item| matches_query(item, search_query));
+    }
 
-    let item = match ItemKind::from(file_name) {
-      ItemKind::Function => Item::Function {
-        name,
-        signature,
-        description,
-      },
-      ItemKind::Struct => Item::Struct {
-        name,
-        signature,
-        description,
-        methods: extract_methods(&document),
-      },
-      ItemKind::Enum => Item::Enum {
-        name,
-        signature,
-        description,
-        variants: extract_enum_variants(&document),
-      },
-      ItemKind::Trait => Item::Trait {
-        name,
-        signature,
-        description,
-        methods: extract_methods(&document),
-      },
-      ItemKind::Macro => Item::Macro {
-        name,
-        signature,
-        description,
-      },
-      ItemKind::Type => Item::Type {
-        name,
-        signature,
-        description,
-      },
-      ItemKind::Constant => Item::Constant {
-        name,
-        signature,
-        description,
-      },
-      ItemKind::Module => Item::Module {
-        name,
-        description,
-        items: extract_module_items(&document),
-      },
-    };
+    items.retain(|item| matches_stability(item, request));
 
-    Ok(Some(item))
+    paginate(merge_duplicates(items), request)
+  } else if path.exists() {
+    lookup_html(&root, &path, request, &mut index)?
   } else {
-    Ok(None)
+    return Err(Error(anyhow!(
+      "documentation not found for crate '{}' at {:?}",
+      request.name,
+      path
+    )));
+  };
+
+  if request.exclude_auto_impls == Some(true) {
+    items
+      .iter_mut()
+      .filter_map(Item::implementations_mut)
+      .for_each(|implementations| {
+        implementations
+          .retain(|implementation| !implementation.kind.is_synthetic());
+      });
   }
+
+  if request.plain_text == Some(true) {
+    items
+      .iter_mut()
+      .for_each(|item| item.map_descriptions(&markdown_to_text));
+  }
+
+  if let Err(error) = cache.store(&key, &index) {
+    warn!("failed to write index cache for '{key}': {error}");
+  }
+
+  Ok(Documentation {
+    name: request.name.to_string(),
+    target,
+    items,
+    ..Default::default()
+  })
 }
 
-fn extract_item_name(file_name: &str) -> Result<String> {
-  file_name
-    .find('.')
-    .ok_or_else(|| Error(anyhow!("invalid file name format")))
-    .and_then(|dot_pos| {
-      file_name[dot_pos + 1..]
-        .strip_suffix(".html")
-        .ok_or_else(|| Error(anyhow!("file name doesn't end with .html")))
-        .map(|name| name.to_string())
-    })
+/// Every item of a crate along with the version rustdoc reports for it, the
+/// form a crate is saved to a snapshot in and compared by `diff_docs`.
+pub fn crate_api(
+  name: &str,
+  path: &str,
+  cache: &Cache,
+) -> Result<Documentation> {
+  let request = LookupCrateRequest {
+    name: name.to_string(),
+    ..Default::default()
+  };
+
+  let mut documentation = lookup_crate(&request, path, cache)?;
+
+  let (root, _) = locate_crate(path, name, None)?;
+
+  let json_path = root.join(format!("{name}.json"));
+
+  documentation.version = if json_path.is_file() {
+    crate_version(&json_path)?
+  } else {
+    fs::read_to_string(root.join(name).join("index.html"))
+      .ok()
+      .and_then(|content| extract_version(&Html::parse_document(&content)))
+  };
+
+  Ok(documentation)
 }
 
-fn extract_methods(document: &Html) -> Vec<Item> {
-  document
-    .select(&Selector::parse(".method-toggle").unwrap())
-    .filter_map(|method_element| {
-      let signature_element = method_element
-        .select(&Selector::parse(".code-header").unwrap())
-        .next()?;
+/// What a crate is about: its root docs, the version rustdoc reports for it,
+/// its top-level modules with one-line summaries, and how many items of each
+/// kind it documents.
+pub fn crate_overview(
+  request: &CrateOverviewRequest,
+  path: &str,
+  cache: &Cache,
+) -> Result<Documentation> {
+  let (root, target) =
+    locate_crate(path, &request.name, request.target.as_deref())?;
 
-      let signature = html_to_text(signature_element.inner_html());
+  let json_path = root.join(format!("{}.json", request.name));
 
-      if signature.is_empty() {
-        return None;
-      }
+  let crate_path = root.join(&request.name);
 
-      let name = extract_method_name(&signature);
+  let mut kinds = Vec::new();
 
-      let description = method_element
-        .select(&Selector::parse(".docblock").unwrap())
-        .next()
-        .map(|desc| html_to_text(desc.inner_html()))
-        .filter(|text| !text.trim().is_empty())
-        .map(|text| text.trim().to_string());
+  let (version, description, modules) = if json_path.is_file() {
+    let lookup = LookupCrateRequest {
+      name: request.name.clone(),
+      target: target.clone(),
+      ..Default::default()
+    };
 
-      Some(Item::Function {
-        name,
-        signature,
-        description,
+    let items = lookup_crate(&lookup, path, cache)?.items;
+
+    kinds.extend(
+      items
+        .iter()
+        .filter(|item| item.path() != request.name)
+        .map(Item::kind),
+    );
+
+    let description = items
+      .iter()
+      .find(|item| item.path() == request.name)
+      .and_then(|item| item.search_items().1.clone());
+
+    let modules = items
+      .iter()
+      .filter(|item| {
+        item.kind() == ItemKind::Module
+          && item
+            .path()
+            .strip_prefix(&format!("{}::", request.name))
+            .is_some_and(|name| !name.contains("::"))
       })
-    })
-    .collect()
-}
+      .map(|item| {
+        let (name, description) = item.search_items();
 
-fn extract_method_name(signature: &str) -> String {
-  signature
-    .strip_prefix("fn ")
-    .and_then(|after_fn| {
-      after_fn
-        .split_once('(')
-        .map(|(name, _)| name.trim().to_string())
-    })
-    .unwrap_or_else(|| "unknown".to_string())
+        ModuleSummary {
+          name: name.clone(),
+          path: item.path().to_string(),
+          summary: description.as_deref().and_then(summary),
+        }
+      })
+      .collect();
+
+    (crate_version(&json_path)?, description, modules)
+  } else if crate_path.exists() {
+    let search_index = load_search_index(&root, &request.name)?;
+
+    kinds.extend(
+      list_entries(&crate_path, &request.name, search_index.as_ref())?
+        .iter()
+        .filter(|entry| entry.path() != request.name)
+        .map(|entry| entry.kind),
+    );
+
+    let document =
+      Html::parse_document(&fs::read_to_string(crate_path.join("index.html"))?);
+
+    (
+      extract_version(&document),
+      extract_description(&document),
+      extract_module_summaries(&document, &request.name),
+    )
+  } else {
+    return Err(Error(anyhow!(
+      "documentation not found for crate '{}' at {:?}",
+      request.name,
+      crate_path
+    )));
+  };
+
+  let mut item_counts = BTreeMap::new();
+
+  for kind in kinds {
+    *item_counts.entry(kind.name().to_string()).or_default() += 1;
+  }
+
+  let description = if request.plain_text == Some(true) {
+    description.as_deref().map(markdown_to_text)
+  } else {
+    description
+  };
+
+  Ok(Documentation {
+    name: request.name.clone(),
+    target,
+    version,
+    description,
+    modules: Some(modules),
+    item_counts: Some(item_counts),
+    items: Vec::new(),
+  })
 }
 
-fn extract_enum_variants(document: &Html) -> Vec<String> {
+/// The version in the sidebar of a crate's root page, which newer rustdoc
+/// renders next to the crate name and older rustdoc as "Version x.y.z".
+fn extract_version(document: &Html) -> Option<String> {
   document
-    .select(&Selector::parse("div.variants .variant").unwrap())
-    .filter_map(|variant_element| {
-      variant_element
-        .select(&Selector::parse(".code-header").unwrap())
-        .next()
-        .map(|header| html_to_text(header.inner_html()))
-        .filter(|variant| !variant.is_empty())
+    .select(selector!(".sidebar-crate .version, .sidebar .version"))
+    .next()
+    .map(|version| {
+      let version = version.text().collect::<String>();
+
+      version
+        .trim()
+        .trim_start_matches("Version ")
+        .trim()
+        .to_string()
     })
-    .collect()
+    .filter(|version| !version.is_empty())
 }
 
-fn extract_module_items(document: &Html) -> Vec<String> {
+/// The modules listed on a crate's root page, with the one-line summary
+/// rustdoc shows beside each.
+fn extract_module_summaries(
+  document: &Html,
+  crate_name: &str,
+) -> Vec<ModuleSummary> {
   document
-    .select(&Selector::parse("div.item-table .item-name a").unwrap())
-    .map(|link| html_to_text(link.inner_html()))
-    .filter(|item| !item.is_empty())
+    .select(selector!(
+      "h2#modules + .item-table dt, h2#modules + .item-table .item-name"
+    ))
+    .filter_map(|element| {
+      let name = element
+        .select(selector!("a"))
+        .next()?
+        .text()
+        .collect::<String>();
+
+      let summary = element
+        .next_siblings()
+        .find_map(ElementRef::wrap)
+        .filter(|sibling| {
+          sibling.value().name() == "dd"
+            || sibling.value().classes().any(|class| class == "desc")
+        })
+        .map(|sibling| html_to_markdown(&sibling.inner_html()))
+        .filter(|summary| !summary.is_empty());
+
+      Some(ModuleSummary {
+        path: format!("{crate_name}::{name}"),
+        name,
+        summary,
+      })
+    })
     .collect()
 }
 
-fn extract_description(document: &Html) -> Option<String> {
-  document
-    .select(&Selector::parse("details.toggle.top-doc div.docblock").unwrap())
+/// The first paragraph of Markdown docs, as rustdoc shows in item tables.
+fn summary(description: &str) -> Option<String> {
+  description
+    .split("\n\n")
     .next()
-    .map(|element| html_to_text(element.inner_html()))
-    .filter(|text| !text.trim().is_empty())
-    .map(|text| text.trim().to_string())
+    .map(str::trim)
+    .filter(|summary| !summary.is_empty())
+    .map(str::to_string)
 }
 
-fn filter_by_item_type(items: Vec<Item>, filter_type: &str) -> Vec<Item> {
-  items
-    .into_iter()
-    .filter(|item| {
-      match item {
-        Item::Function { .. } => "function",
-        Item::Struct { .. } => "struct",
-        Item::Enum { .. } => "enum",
-        Item::Trait { .. } => "trait",
-        Item::Macro { .. } => "macro",
-        Item::Type { .. } => "type",
-        Item::Constant { .. } => "constant",
-        Item::Module { .. } => "module",
+/// Find examples whose code mentions `query` as a whole identifier, across
+/// every documented crate or just the one named in the request.
+pub fn search_examples(
+  request: &SearchExamplesRequest,
+  path: &str,
+  std_path: Option<&str>,
+  cache: &Cache,
+) -> Result<Vec<ExampleMatch>> {
+  let lookups = crate_lookups(request.name.as_deref(), path, std_path)?;
+
+  let boundary = |c: Option<char>| {
+    if c.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+      r"\b"
+    } else {
+      ""
+    }
+  };
+
+  let pattern = Regex::new(&format!(
+    "{}{}{}",
+    boundary(request.query.chars().next()),
+    regex::escape(&request.query),
+    boundary(request.query.chars().last())
+  ))?;
+
+  let mut matches = Vec::new();
+
+  for (lookup, path) in lookups {
+    let documentation = match lookup_crate(&lookup, path, cache) {
+      Ok(documentation) => documentation,
+      Err(error) => {
+        warn!("skipping examples for '{}': {error}", lookup.name);
+        continue;
       }
-      .eq_ignore_ascii_case(filter_type)
-    })
-    .collect()
+    };
+
+    matches.extend(
+      documentation
+        .items
+        .iter()
+        .flat_map(Item::nested_examples)
+        .filter(|(_, example)| pattern.is_match(&example.code))
+        .map(|(path, example)| ExampleMatch {
+          path: path.to_string(),
+          example: example.clone(),
+        }),
+    );
+  }
+
+  Ok(
+    matches
+      .into_iter()
+      .take(request.limit.unwrap_or(usize::MAX))
+      .collect(),
+  )
 }
 
-fn filter_by_query(items: Vec<Item>, query: &str) -> Vec<Item> {
-  let query_lower = query.to_lowercase();
+/// The source of the item documented at `request.path`: the lines of its
+/// definition by default, or the whole file or a range of it when asked.
+/// Methods are found through the type or trait they belong to.
+pub fn get_source(
+  request: &GetSourceRequest,
+  path: &str,
+  workspace_root: Option<&Path>,
+  cache: &Cache,
+) -> Result<SourceCode> {
+  let item_path = request.path.trim_end_matches("::");
 
-  items
-    .into_iter()
-    .filter(|item| {
-      let (name, description) = item.search_items();
+  let name = item_path.split("::").next().unwrap_or_default();
 
-      name.to_lowercase().contains(&query_lower)
-        || description.as_ref().is_some_and(|description| {
-          description.to_lowercase().contains(&query_lower)
-        })
-    })
-    .collect()
-}
+  let parent = item_path.rsplit_once("::").map(|(parent, _)| parent);
 
-fn html_to_text(html: String) -> String {
-  [
-    |text: String| {
-      Regex::new(r"<[^>]*>")
-        .unwrap()
-        .replace_all(&text, "")
-        .into_owned()
-    },
-    |text: String| {
-      [
-        ("&amp;", "&"),
-        ("&lt;", "<"),
-        ("&gt;", ">"),
-        ("&quot;", "\""),
-        ("&#39;", "'"),
-      ]
-      .iter()
-      .fold(text, |acc, &(entity, replacement)| {
-        acc.replace(entity, replacement)
-      })
-    },
-    |text: String| {
-      Regex::new(r"\s+")
-        .unwrap()
-        .replace_all(&text, " ")
-        .trim()
-        .to_string()
-    },
-  ]
-  .iter()
-  .fold(html, |text, transform| transform(text))
-}
+  let mut item = None;
 
-#[cfg(test)]
-mod tests {
-  use {super::*, std::fs, tempfile::TempDir};
+  for prefix in [Some(item_path), parent].into_iter().flatten() {
+    let lookup = LookupCrateRequest {
+      name: name.to_string(),
+      path: Some(prefix.to_string()),
+      target: request.target.clone(),
+      ..Default::default()
+    };
 
-  fn initialize(temp_dir: &TempDir) -> String {
-    let doc_path = temp_dir.path().join("target").join("doc");
-    fs::create_dir_all(&doc_path).unwrap();
-    fs::create_dir_all(doc_path.join("crate")).unwrap();
-    doc_path.to_string_lossy().to_string()
+    item = lookup_crate(&lookup, path, cache)?
+      .items
+      .iter()
+      .find_map(|item| item.find(item_path).cloned());
+
+    if item.is_some() {
+      break;
+    }
   }
 
-  fn function_html(
-    name: &str,
-    signature: &str,
-    description: Option<&str>,
-  ) -> String {
-    let desc_html = description
-      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
-      .unwrap_or_default();
+  let item = item.ok_or_else(|| anyhow!("no item found at '{item_path}'"))?;
 
-    format!(
-      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
-      <pre class="rust item-decl"><code>{}</code></pre>
-      {}
-      </body></html>"#,
+  let location = item
+    .source()
+    .ok_or_else(|| anyhow!("no source location recorded for '{item_path}'"))?;
+
+  let (file, lines) = location.split_once('#').unwrap_or((location, ""));
+
+  let span = {
+    let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+    start.parse::<usize>().ok().zip(end.parse::<usize>().ok())
+  };
+
+  let (root, _) = locate_crate(path, name, request.target.as_deref())?;
+
+  let source = source_lines(&root, file, workspace_root)?;
+
+  let (start_line, end_line) = match (request.start_line, request.end_line) {
+    (None, None) if request.whole_file != Some(true) => {
+      span.unwrap_or((1, source.len()))
+    }
+    (start, end) => (start.unwrap_or(1), end.unwrap_or(source.len())),
+  };
+
+  let end_line = end_line.min(source.len());
+
+  if start_line == 0 || start_line > end_line {
+    return Err(Error(anyhow!(
+      "lines {start_line}-{end_line} are outside of {file}, which has {} lines",
+      source.len()
+    )));
+  }
+
+  Ok(SourceCode {
+    path: item.path().to_string(),
+    file: file.to_string(),
+    start_line,
+    end_line,
+    code: source[start_line - 1..end_line].join("\n"),
+  })
+}
+
+/// The lines of a source file, taken from the highlighted page rustdoc
+/// renders under `src/`, or from the file itself for the spans in rustdoc
+/// JSON, which point at the original sources relative to the workspace root.
+/// Without a known root, the directory holding `target/` stands in for it.
+fn source_lines(
+  root: &Path,
+  file: &str,
+  workspace_root: Option<&Path>,
+) -> Result<Vec<String>> {
+  let page = root.join("src").join(format!("{file}.html"));
+
+  let content = if page.is_file() {
+    let document = Html::parse_document(&fs::read_to_string(&page)?);
+
+    let code = document
+      .select(selector!("pre.rust code"))
+      .next()
+      .ok_or_else(|| anyhow!("no source code found in {page:?}"))?;
+
+    // Newer rustdoc renders each line number as an anchor inside the code.
+    code
+      .descendants()
+      .filter(|node| {
+        node
+          .parent()
+          .and_then(ElementRef::wrap)
+          .is_none_or(|parent| parent.value().attr("data-nosnippet").is_none())
+      })
+      .filter_map(|node| node.value().as_text().map(|text| &**text))
+      .collect::<String>()
+  } else if let Some(file) = source_file(root, file, workspace_root) {
+    fs::read_to_string(file)?
+  } else {
+    return Err(Error(anyhow!("source file not found for '{file}'")));
+  };
+
+  Ok(content.lines().map(str::to_string).collect())
+}
+
+fn source_file(
+  root: &Path,
+  file: &str,
+  workspace_root: Option<&Path>,
+) -> Option<PathBuf> {
+  if Path::new(file).is_absolute() {
+    return Some(PathBuf::from(file)).filter(|file| file.is_file());
+  }
+
+  workspace_root
+    .into_iter()
+    .chain(
+      root
+        .ancestors()
+        .filter(|directory| directory.ends_with("target"))
+        .filter_map(Path::parent),
+    )
+    .map(|directory| directory.join(file))
+    .find(|file| file.is_file())
+}
+
+fn lookup_html(
+  root: &Path,
+  path: &Path,
+  request: &LookupCrateRequest,
+  index: &mut CrateIndex,
+) -> Result<Vec<Item>> {
+  let search_index = load_search_index(root, &request.name)?;
+
+  let mut entries = list_entries(path, &request.name, search_index.as_ref())?;
+
+  index.retain(&entries.iter().map(|entry| entry.file.as_path()).collect());
+
+  if let Some(ref prefix) = request.path {
+    entries.retain(|entry| has_path_prefix(&entry.path(), prefix));
+  }
+
+  if let Some(ref filter_type) = request.item_type {
+    entries.retain(|entry| entry.kind.name().eq_ignore_ascii_case(filter_type));
+  }
+
+  // Stability badges only exist on the rendered pages, and copies of an item
+  // are only recognised once parsed, so pages are parsed until a page's worth
+  // of unique items pass.
+  match (&request.query, search_index) {
+    (Some(search_query), None) => {
+      parse_matching(&entries, index, request, |item| {
+        matches_query(item, search_query) && matches_stability(item, request)
+      })
+    }
+    (search_query, _) => {
+      if let Some(search_query) = search_query {
+        entries = filter_entries_by_query(entries, search_query);
+      }
+
+      parse_matching(&entries, index, request, |item| {
+        matches_stability(item, request)
+      })
+    }
+  }
+}
+
+/// Collapse an item documented at several paths, as happens with inlined
+/// re-exports, into the copy with the shortest path, keeping the others as
+/// aliases. Copies are recognised by sharing a kind and source location.
+fn merge_duplicates(items: Vec<Item>) -> Vec<Item> {
+  let mut merged = Vec::<Item>::new();
+
+  let mut seen = HashMap::new();
+
+  for mut item in items {
+    let Some(key) = item
+      .source()
+      .map(|source| (item.kind(), source.to_string()))
+    else {
+      merged.push(item);
+      continue;
+    };
+
+    let Some(&position) = seen.get(&key) else {
+      seen.insert(key, merged.len());
+      merged.push(item);
+      continue;
+    };
+
+    let existing = &mut merged[position];
+
+    let depth = |item: &Item| item.path().matches("::").count();
+
+    if depth(&item) < depth(existing) {
+      mem::swap(existing, &mut item);
+    }
+
+    let path = existing.path().to_string();
+
+    let mut aliases = mem::take(item.aliases_mut());
+
+    aliases.push(item.path().to_string());
+
+    let existing = existing.aliases_mut();
+
+    existing.extend(aliases);
+    existing.retain(|alias| *alias != path);
+    existing.sort();
+    existing.dedup();
+  }
+
+  merged
+}
+
+fn has_path_prefix(path: &str, prefix: &str) -> bool {
+  let prefix = prefix.trim_end_matches("::");
+
+  path
+    .strip_prefix(prefix)
+    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+fn paginate<T>(items: Vec<T>, request: &LookupCrateRequest) -> Vec<T> {
+  items
+    .into_iter()
+    .skip(request.offset.unwrap_or(0))
+    .take(request.limit.unwrap_or(usize::MAX))
+    .collect()
+}
+
+/// The pages documenting a crate's items, taken from the search index when
+/// it has them all, since that avoids walking and reading through the whole
+/// tree. Redirects left where re-exported items are defined are only found
+/// by walking it, so then items have no aliases from them.
+fn list_entries(
+  path: &Path,
+  crate_name: &str,
+  search_index: Option<&SearchIndex>,
+) -> Result<Vec<Entry>> {
+  let indexed = search_index
+    .and_then(|search_index| search_index.entries.clone())
+    .filter(|entries| entries.iter().all(|entry| entry.file.is_file()));
+
+  let mut entries = match indexed {
+    Some(entries) => entries,
+    None => walk_entries(path, crate_name)?,
+  };
+
+  entries.sort_by_cached_key(|entry| entry.path());
+
+  if let Some(search_index) = search_index {
+    for entry in &mut entries {
+      entry.description = search_index.descriptions.get(&entry.path()).cloned();
+    }
+  }
+
+  Ok(entries)
+}
+
+/// The pages found by walking the crate's documentation directory, leaving
+/// out redirects and, when `all.html` exists, pages it doesn't link to.
+fn walk_entries(path: &Path, crate_name: &str) -> Result<Vec<Entry>> {
+  let listed = list_all_items(path)?;
+
+  let mut entries = Vec::new();
+
+  collect_entries(path, crate_name, &mut entries)?;
+
+  let mut redirects = HashMap::<PathBuf, Vec<String>>::new();
+
+  // Redirects are also left in private modules, which have no index page and
+  // whose paths can't be used from outside the crate.
+  entries.retain(|entry| match redirect_target(&entry.file) {
+    Some(target) => {
+      if entry
+        .file
+        .parent()
+        .is_some_and(|module| module.join("index.html").is_file())
+      {
+        redirects.entry(target).or_default().push(entry.path());
+      }
+
+      false
+    }
+    None => true,
+  });
+
+  if let Some(listed) = listed {
+    entries.retain(|entry| {
+      // `all.html` leaves out keywords, which only std documents.
+      matches!(entry.kind, ItemKind::Module | ItemKind::Keyword)
+        || entry
+          .file
+          .strip_prefix(path)
+          .is_ok_and(|relative| listed.contains(relative))
+    });
+  }
+
+  for entry in &mut entries {
+    let path = entry.path();
+
+    // Old-style `macro.name!.html` redirects only differ by the `!`.
+    entry.aliases = redirects
+      .remove(&entry.file)
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|alias| alias.trim_end_matches('!') != path)
+      .collect();
+  }
+
+  Ok(entries)
+}
+
+fn collect_entries(
+  dir: &Path,
+  module: &str,
+  entries: &mut Vec<Entry>,
+) -> Result {
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str())
+    else {
+      continue;
+    };
+
+    if path.is_dir() {
+      collect_entries(&path, &format!("{module}::{file_name}"), entries)?;
+      continue;
+    }
+
+    let Some(kind) = ItemKind::from_file_name(file_name) else {
+      continue;
+    };
+
+    let (parent, name) = if kind == ItemKind::Module {
+      let (parent, name) = module.rsplit_once("::").unwrap_or(("", module));
+      (parent.to_string(), name.to_string())
+    } else {
+      match extract_item_name(file_name) {
+        Ok(name) => (module.to_string(), name),
+        Err(_) => continue,
+      }
+    };
+
+    entries.push(Entry {
+      aliases: Vec::new(),
+      description: None,
+      kind,
+      module: parent,
+      name,
+      file: path,
+    });
+  }
+
+  Ok(())
+}
+
+/// The page a rustdoc redirect page forwards to, such as the one left at an
+/// item's defining module when it is documented under a re-export. Redirects
+/// are tiny, so larger files are never read.
+fn redirect_target(file: &Path) -> Option<PathBuf> {
+  if fs::metadata(file).ok()?.len() > 2048 {
+    return None;
+  }
+
+  let content = fs::read_to_string(file).ok()?;
+
+  let target = regex!(r##"http-equiv="refresh" content="0;URL=([^"#]+)"##)
+    .captures(&content)?[1]
+    .to_string();
+
+  let mut resolved = PathBuf::new();
+
+  for component in file.parent()?.join(target).components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        resolved.pop();
+      }
+      component => resolved.push(component),
+    }
+  }
+
+  Some(resolved)
+}
+
+/// The set of pages linked from rustdoc's `all.html`, relative to the crate
+/// directory. Pages missing from it, such as redirects, aren't items.
+fn list_all_items(path: &Path) -> Result<Option<HashSet<PathBuf>>> {
+  let all = path.join("all.html");
+
+  if !all.is_file() {
+    return Ok(None);
+  }
+
+  let document = Html::parse_document(&fs::read_to_string(all)?);
+
+  Ok(Some(
+    document
+      .select(selector!("ul.all-items a"))
+      .filter_map(|link| link.value().attr("href"))
+      .map(PathBuf::from)
+      .collect(),
+  ))
+}
+
+fn parse_entries(
+  entries: &[&Entry],
+  index: &mut CrateIndex,
+) -> Result<Vec<Item>> {
+  let items = index.par_items(entries, |entry| {
+    Ok(
+      parse_html_file(&entry.file, &entry.path())?
+        .into_iter()
+        .collect(),
+    )
+  })?;
+
+  Ok(
+    entries
+      .iter()
+      .zip(items)
+      .flat_map(|(entry, items)| {
+        items.into_iter().map(|mut item| {
+          item.aliases_mut().extend(entry.aliases.iter().cloned());
+          item
+        })
+      })
+      .collect(),
+  )
+}
+
+/// Parse `entries` a batch at a time, keeping the items `keep` accepts until
+/// the requested page is full. Entries sharing a kind and name may be inlined
+/// copies of one item, so they are parsed along with the first of them and
+/// merged before counting towards the page. Only one batch of parsed pages is
+/// held at once, and pages past the end of the requested page are only parsed
+/// when they are copies of an item on it.
+fn parse_matching(
+  entries: &[Entry],
+  index: &mut CrateIndex,
+  request: &LookupCrateRequest,
+  keep: impl Fn(&Item) -> bool,
+) -> Result<Vec<Item>> {
+  let mut copies = HashMap::<(ItemKind, &str), Vec<usize>>::new();
+
+  for (position, entry) in entries.iter().enumerate() {
+    copies
+      .entry((entry.kind, entry.name.as_str()))
+      .or_default()
+      .push(position);
+  }
+
+  let mut parsed = vec![false; entries.len()];
+
+  let mut skip = request.offset.unwrap_or(0);
+
+  let limit = request.limit.unwrap_or(usize::MAX);
+
+  let mut items = Vec::new();
+
+  let mut start = 0;
+
+  while start < entries.len() && items.len() < limit {
+    // Each page holds at most one item, so no more are parsed than could
+    // still land on the requested page.
+    let end = entries.len().min(
+      start + PARSE_BATCH_SIZE.min(skip.saturating_add(limit - items.len())),
+    );
+
+    let mut batch = Vec::new();
+
+    for entry in &entries[mem::replace(&mut start, end)..end] {
+      for &position in &copies[&(entry.kind, entry.name.as_str())] {
+        if !mem::replace(&mut parsed[position], true) {
+          batch.push(&entries[position]);
+        }
+      }
+    }
+
+    for item in merge_duplicates(parse_entries(&batch, index)?)
+      .into_iter()
+      .filter(&keep)
+    {
+      if skip > 0 {
+        skip -= 1;
+      } else if items.len() < limit {
+        items.push(item);
+      }
+    }
+  }
+
+  Ok(items)
+}
+
+fn parse_html_file(file_path: &Path, path: &str) -> Result<Option<Item>> {
+  let document = Html::parse_document(&fs::read_to_string(file_path)?);
+
+  let file_name = file_path
+    .file_name()
+    .and_then(|n| n.to_str())
+    .ok_or_else(|| anyhow!("invalid file name"))?;
+
+  let Some(kind) = ItemKind::from_file_name(file_name) else {
+    return Ok(None);
+  };
+
+  let signature = document
+    .select(selector!("pre.rust.item-decl"))
+    .next()
+    .map(|element| html_to_text(element.inner_html()))
+    .filter(|signature| !signature.is_empty());
+
+  let description = extract_description(&document);
+
+  let stability = extract_stability(&document);
+
+  let examples = top_docblock(&document)
+    .map(extract_examples)
+    .unwrap_or_default();
+
+  let links = document
+    .select(selector!("pre.rust.item-decl a[href]"))
+    .filter_map(|link| link.value().attr("href"))
+    .map(str::to_string)
+    .collect::<Vec<_>>();
+
+  let source = document
+    .select(selector!(
+      ".main-heading a.src, .main-heading a.srclink, .out-of-band a.srclink"
+    ))
+    .next()
+    .and_then(source_location);
+
+  let module = match kind {
+    ItemKind::Module => path,
+    _ => path.rsplit_once("::").map_or(path, |(module, _)| module),
+  }
+  .to_string();
+
+  let name = path.rsplit("::").next().unwrap_or(path).to_string();
+
+  let path = path.to_string();
+
+  let mut item = match (kind, signature) {
+    (ItemKind::Module, _) => Item::Module {
+      reexports: extract_reexports(&document, &path),
+      name,
+      path,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+      items: extract_module_items(&document),
+    },
+    (ItemKind::Primitive, _) => Item::Primitive {
+      implementations: extract_implementations(&document, &path),
+      name,
+      path,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Keyword, _) => Item::Keyword {
+      name,
+      path,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (_, None) => return Ok(None),
+    (ItemKind::Function, Some(signature)) => Item::Function {
+      name,
+      path,
+      parsed_signature: FunctionSignature::parse(&signature),
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Struct, Some(signature)) => Item::Struct {
+      fields: extract_fields(&document, &signature),
+      implementations: extract_implementations(&document, &path),
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Enum, Some(signature)) => Item::Enum {
+      implementations: extract_implementations(&document, &path),
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+      variants: extract_enum_variants(&document),
+    },
+    (ItemKind::Trait, Some(signature)) => Item::Trait {
+      supertraits: extract_supertraits(&signature),
+      required_methods: trait_sections(
+        &document,
+        selector!("h2#required-methods + div.methods section.method"),
+      )
+      .into_iter()
+      .filter_map(|section| method_item(section, &path))
+      .collect(),
+      provided_methods: trait_sections(
+        &document,
+        selector!("h2#provided-methods + div.methods section.method"),
+      )
+      .into_iter()
+      .filter_map(|section| method_item(section, &path))
+      .collect(),
+      associated_types: extract_associated_types(&document),
+      associated_constants: extract_associated_constants(&document),
+      dyn_compatible: document
+        .select(selector!("#dyn-compatibility, #object-safety"))
+        .next()
+        .is_none(),
+      implementors: extract_implementors(
+        &document,
+        path.split("::").next().unwrap_or_default(),
+      ),
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Macro, Some(signature)) => Item::Macro {
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Type, Some(signature)) => Item::Type {
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Constant, Some(signature)) => Item::Constant {
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Union, Some(signature)) => Item::Union {
+      fields: extract_fields(&document, &signature),
+      implementations: extract_implementations(&document, &path),
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::Static, Some(signature)) => Item::Static {
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::TraitAlias, Some(signature)) => Item::TraitAlias {
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::AttributeMacro, Some(signature)) => Item::AttributeMacro {
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::DeriveMacro, Some(signature)) => Item::DeriveMacro {
+      helper_attributes: extract_helper_attributes(&signature),
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+    (ItemKind::ForeignType, Some(signature)) => Item::ForeignType {
+      implementations: extract_implementations(&document, &path),
+      name,
+      path,
+      signature,
+      description,
+      stability,
+      examples,
+      links,
+      aliases: Vec::new(),
+      source,
+    },
+  };
+
+  let page = item.path().to_string();
+
+  item.resolve_links(&|href| resolve_href(href, &module, &page));
+
+  Ok(Some(item))
+}
+
+/// Helper attributes are listed in a derive macro's declaration after an
+/// "Attributes available to this derive:" comment.
+fn extract_helper_attributes(signature: &str) -> Vec<String> {
+  signature
+    .split_once("Attributes available to this derive:")
+    .map(|(_, helpers)| {
+      regex!(r"#\[(\w+)\]")
+        .captures_iter(helpers)
+        .map(|captures| captures[1].to_string())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn extract_item_name(file_name: &str) -> Result<String> {
+  file_name
+    .find('.')
+    .ok_or_else(|| Error(anyhow!("invalid file name format")))
+    .and_then(|dot_pos| {
+      file_name[dot_pos + 1..]
+        .strip_suffix(".html")
+        .ok_or_else(|| Error(anyhow!("file name doesn't end with .html")))
+        .map(|name| name.to_string())
+    })
+}
+
+fn extract_implementations(
+  document: &Html,
+  parent: &str,
+) -> Vec<Implementation> {
+  [
+    (
+      selector!("#implementations-list section.impl"),
+      ImplementationKind::Inherent,
+    ),
+    (
+      selector!("#trait-implementations-list section.impl"),
+      ImplementationKind::Trait,
+    ),
+    (
+      selector!("#synthetic-implementations-list section.impl"),
+      ImplementationKind::Auto,
+    ),
+    (
+      selector!("#blanket-implementations-list section.impl"),
+      ImplementationKind::Blanket,
+    ),
+  ]
+  .into_iter()
+  .flat_map(|(selector, kind)| {
+    document
+      .select(selector)
+      .filter_map(|section| {
+        let signature = code_header(section)?;
+
+        // Impls with items are wrapped in a `details` toggle whose summary
+        // holds the header, followed by the `.impl-items` block.
+        let methods = section
+          .parent()
+          .and_then(ElementRef::wrap)
+          .filter(|summary| summary.value().name() == "summary")
+          .and_then(|summary| summary.parent().and_then(ElementRef::wrap))
+          .map(|toggle| extract_impl_methods(toggle, parent))
+          .unwrap_or_default();
+
+        let (generics, where_clause) = impl_generics(&signature);
+
+        Some(Implementation {
+          trait_name: split_impl_header(&signature)
+            .and_then(|(_, trait_name, _, _)| trait_name)
+            .map(str::to_string),
+          generics,
+          where_clause,
+          signature,
+          kind,
+          methods,
+        })
+      })
+      .collect::<Vec<_>>()
+  })
+  .collect()
+}
+
+fn extract_impl_methods(toggle: ElementRef, parent: &str) -> Vec<Item> {
+  toggle
+    .select(selector!(".impl-items section.method"))
+    .filter_map(|section| method_item(section, parent))
+    .collect()
+}
+
+/// A method `section`, whose docs follow its enclosing `summary` when the
+/// method is documented.
+fn method_item(section: ElementRef, parent: &str) -> Option<Item> {
+  let signature = code_header(section)?;
+
+  let name = extract_method_name(&signature);
+
+  let summary = section
+    .parent()
+    .and_then(ElementRef::wrap)
+    .filter(|summary| summary.value().name() == "summary");
+
+  let docblock = summary.and_then(sibling_docblock_element);
+
+  let (since, const_since) = section
+    .select(selector!(".since"))
+    .next()
+    .map(since)
+    .unwrap_or_default();
+
+  Some(Item::Function {
+    path: format!("{parent}::{name}"),
+    name,
+    parsed_signature: FunctionSignature::parse(&signature),
+    signature,
+    description: docblock
+      .map(|docblock| html_to_markdown(&docblock.inner_html()))
+      .filter(|text| !text.is_empty()),
+    examples: docblock.map(extract_examples).unwrap_or_default(),
+    links: section
+      .select(selector!(".code-header a[href]"))
+      .filter_map(|link| link.value().attr("href"))
+      .map(str::to_string)
+      .collect(),
+    aliases: Vec::new(),
+    source: section
+      .select(selector!("a.src, a.srclink"))
+      .next()
+      .and_then(source_location),
+    stability: Stability {
+      since,
+      const_since,
+      ..summary
+        .and_then(|summary| {
+          summary
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .take_while(|sibling| {
+              !sibling.value().classes().any(|class| class == "docblock")
+            })
+            .find(|sibling| {
+              sibling.value().classes().any(|class| class == "item-info")
+            })
+        })
+        .map(stability)
+        .unwrap_or_default()
+    },
+  })
+}
+
+/// Resolve a link on the page of the item at `page`, which lives in
+/// `module`, into the path of the item it points at. Links to members of an
+/// item, such as `struct.Foo.html#method.bar`, resolve to the member's path.
+fn resolve_href(href: &str, module: &str, page: &str) -> Option<String> {
+  if href.contains("://") || href.starts_with('/') {
+    return None;
+  }
+
+  let (file, fragment) = href.split_once('#').unwrap_or((href, ""));
+
+  let (directories, file_name) = file.rsplit_once('/').unwrap_or(("", file));
+
+  let mut segments = module.split("::").collect::<Vec<_>>();
+
+  for directory in directories.split('/') {
+    match directory {
+      "" | "." => {}
+      ".." => {
+        segments.pop()?;
+      }
+      directory => segments.push(directory),
+    }
+  }
+
+  let mut path = match file_name {
+    "" => page.to_string(),
+    "index.html" => segments.join("::"),
+    file_name => {
+      ItemKind::from_file_name(file_name)?;
+      segments.push(file_name.split('.').nth(1)?);
+      segments.join("::")
+    }
+  };
+
+  if let Some((kind, member)) = fragment.split_once('.')
+    && matches!(
+      kind,
+      "method"
+        | "tymethod"
+        | "variant"
+        | "structfield"
+        | "associatedtype"
+        | "associatedconstant"
+    )
+  {
+    path = format!("{path}::{member}");
+  }
+
+  (!path.is_empty() && path != page).then_some(path)
+}
+
+/// Where a `Source` link points in the rendered `src/` tree, as the source
+/// file relative to it along with the line range, e.g.
+/// `std/collections/hash/map.rs#246-253`.
+fn source_location(link: ElementRef) -> Option<String> {
+  let href = link.value().attr("href")?;
+
+  let (file, lines) = href.split_once('#').unwrap_or((href, ""));
+
+  let file = file
+    .trim_start_matches("../")
+    .strip_prefix("src/")?
+    .trim_end_matches(".html");
+
+  Some(if lines.is_empty() {
+    file.to_string()
+  } else {
+    format!("{file}#{lines}")
+  })
+}
+
+/// The `pub use` declarations listed in a module's "Re-exports" table. The
+/// original path comes from the link rustdoc renders for the target, falling
+/// back to the path as written.
+fn extract_reexports(document: &Html, module: &str) -> Vec<ReExport> {
+  document
+    .select(selector!(".reexports code"))
+    .filter_map(|code| {
+      let text = html_to_text(code.inner_html());
+
+      let declaration = text.strip_prefix("pub use ")?.trim_end_matches(';');
+
+      let (written, rename) = match declaration.split_once(" as ") {
+        Some((written, rename)) => (written.trim(), Some(rename.trim())),
+        None => (declaration.trim(), None),
+      };
+
+      let glob = written.ends_with("::*");
+
+      let original = code
+        .select(selector!("a[title]"))
+        .last()
+        .and_then(|link| link.value().attr("title")?.split_once(' '))
+        .map(|(_, path)| {
+          if glob {
+            format!("{path}::*")
+          } else {
+            path.to_string()
+          }
+        })
+        .unwrap_or_else(|| absolute_path(written, module));
+
+      let name = rename
+        .unwrap_or_else(|| written.rsplit("::").next().unwrap_or(written));
+
+      Some(ReExport::new(format!("{module}::{name}"), original))
+    })
+    .collect()
+}
+
+/// Resolve the `crate`, `self` and `super` prefixes of a path written in
+/// `module`.
+fn absolute_path(written: &str, module: &str) -> String {
+  let mut segments = Vec::new();
+
+  for (index, segment) in written.split("::").enumerate() {
+    match (index, segment) {
+      (0, "crate") => {
+        segments.push(module.split("::").next().unwrap_or(module))
+      }
+      (0, "self") => segments.extend(module.split("::")),
+      (0, "super") => {
+        segments.extend(module.split("::"));
+        segments.pop();
+      }
+      (_, "super") if segments.len() > 1 => {
+        segments.pop();
+      }
+      (_, segment) => segments.push(segment),
+    }
+  }
+
+  segments.join("::")
+}
+
+/// The text of an item's header. rustdoc puts its `where` clause in a block
+/// right after the declaration, which would otherwise run into a trailing
+/// identifier, as in `impl<T> ToString for Twhere T: Display`.
+fn code_header(element: ElementRef) -> Option<String> {
+  element
+    .select(selector!(".code-header"))
+    .next()
+    .map(|header| {
+      html_to_text(
+        header
+          .inner_html()
+          .replace(r#"<div class="where">"#, r#" <div class="where">"#),
+      )
+    })
+    .filter(|header| !header.is_empty())
+}
+
+fn section_docblock(section: ElementRef) -> Option<String> {
+  section
+    .parent()
+    .and_then(ElementRef::wrap)
+    .filter(|summary| summary.value().name() == "summary")
+    .and_then(sibling_docblock)
+}
+
+/// The member sections rendered under trait page headings, such as
+/// `required-methods` or `provided-associated-types`, in page order.
+fn trait_sections<'a>(
+  document: &'a Html,
+  selector: &Selector,
+) -> Vec<ElementRef<'a>> {
+  document.select(selector).collect()
+}
+
+fn extract_associated_types(document: &Html) -> Vec<AssociatedType> {
+  trait_sections(
+    document,
+    selector!(
+      "h2#required-associated-types + div.methods section.method, \
+       h2#provided-associated-types + div.methods section.method"
+    ),
+  )
+  .into_iter()
+  .filter_map(|section| {
+    let signature = code_header(section)?;
+
+    let (declaration, _) = split_where_clause(signature.strip_prefix("type ")?);
+
+    let (declaration, default) = match split_top_level(declaration, '=')[..] {
+      [declaration, default] => (declaration, Some(default.to_string())),
+      _ => (declaration, None),
+    };
+
+    let (name, rest) = split_identifier(declaration);
+
+    let (_, rest) = split_generics(rest);
+
+    Some(AssociatedType {
+      name: name.to_string(),
+      bounds: rest
+        .trim()
+        .strip_prefix(':')
+        .map(|bounds| {
+          split_top_level(bounds, '+')
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+        })
+        .unwrap_or_default(),
+      default,
+      description: section_docblock(section),
+      signature,
+    })
+  })
+  .collect()
+}
+
+fn extract_associated_constants(document: &Html) -> Vec<AssociatedConstant> {
+  trait_sections(
+    document,
+    selector!(
+      "h2#required-associated-consts + div.methods section.method, \
+       h2#provided-associated-consts + div.methods section.method"
+    ),
+  )
+  .into_iter()
+  .filter_map(|section| {
+    let signature = code_header(section)?;
+
+    let declaration = signature.strip_prefix("const ")?;
+
+    let (declaration, default) = match split_top_level(declaration, '=')[..] {
+      [declaration, default] => (declaration, Some(default.to_string())),
+      _ => (declaration, None),
+    };
+
+    let (name, ty) = declaration.split_once(':')?;
+
+    Some(AssociatedConstant {
+      name: name.trim().to_string(),
+      ty: ty.trim().to_string(),
+      default,
+      description: section_docblock(section),
+      signature,
+    })
+  })
+  .collect()
+}
+
+/// Supertraits from a trait declaration such as
+/// `pub trait Sub<T>: Super + Clone where T: Send { ... }`.
+fn extract_supertraits(signature: &str) -> Vec<String> {
+  let Some((_, declaration)) = signature.split_once("trait ") else {
+    return Vec::new();
+  };
+
+  let (_, rest) = split_identifier(declaration);
+
+  let (_, rest) = split_generics(rest);
+
+  rest
+    .trim_start()
+    .strip_prefix(':')
+    .map(|bounds| {
+      let (bounds, _) =
+        split_where_clause(bounds.split('{').next().unwrap_or_default());
+
+      split_top_level(bounds, '+')
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn split_identifier(declaration: &str) -> (&str, &str) {
+  let declaration = declaration.trim_start();
+
+  declaration.split_at(
+    declaration
+      .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+      .unwrap_or(declaration.len()),
+  )
+}
+
+pub fn implementor(crate_name: &str, signature: &str) -> Option<Implementor> {
+  let (_, _, ty, _) = split_impl_header(signature)?;
+
+  Some(Implementor {
+    crate_name: crate_name.to_string(),
+    signature: signature.to_string(),
+    ty: ty.to_string(),
+  })
+}
+
+/// Implementors listed on a trait page, including implementations on foreign
+/// types. Those are defined in the trait's own crate, `crate_name`, while the
+/// rest are attributed to the crate of the type they are for, falling back to
+/// `crate_name` for blanket impls.
+pub fn extract_implementors(
+  document: &Html,
+  crate_name: &str,
+) -> Vec<Implementor> {
+  let headers = |selector: &Selector| {
+    document
+      .select(selector)
+      .filter_map(|section| section.select(selector!(".code-header")).next())
+      .collect::<Vec<_>>()
+  };
+
+  let foreign = headers(selector!(
+    "#foreign-impls ~ details section.impl, \
+     #foreign-impls ~ section.impl"
+  ))
+  .into_iter()
+  .filter_map(|header| {
+    implementor(crate_name, &html_to_text(header.inner_html()))
+  });
+
+  let implementors = headers(selector!(
+    "#implementors-list section.impl, \
+     #synthetic-implementors-list section.impl"
+  ))
+  .into_iter()
+  .filter_map(|header| {
+    implementor(
+      &self_type_crate(header).unwrap_or_else(|| crate_name.to_string()),
+      &html_to_text(header.inner_html()),
+    )
+  });
+
+  foreign.chain(implementors).collect()
+}
+
+/// The crate of the type an impl header is for, read from the title rustdoc
+/// gives the first link after `for`, such as `struct other::Wrapper`.
+/// Primitives and bare type parameters have no such path.
+fn self_type_crate(header: ElementRef) -> Option<String> {
+  let mut after_for = false;
+
+  for node in header.children() {
+    if let Some(text) = node.value().as_text() {
+      after_for |= regex!(r"(^|\s)for(\s|$)").is_match(text);
+    } else if after_for {
+      let (_, path) = ElementRef::wrap(node)?
+        .value()
+        .attr("title")?
+        .split_once(' ')?;
+
+      return path.split_once("::").map(|(name, _)| name.to_string());
+    }
+  }
+
+  None
+}
+
+fn extract_fields(document: &Html, signature: &str) -> Vec<Field> {
+  document
+    .select(selector!(".structfield"))
+    .filter_map(|element| {
+      let (name, ty) = element
+        .select(selector!("code"))
+        .next()
+        .map(|code| html_to_text(code.inner_html()))?
+        .split_once(':')
+        .map(|(name, ty)| (name.trim().to_string(), ty.trim().to_string()))?;
+
+      Some(Field {
+        visibility: field_visibility(signature, &name),
+        description: sibling_docblock(element),
+        name,
+        ty,
+      })
+    })
+    .collect()
+}
+
+/// Rustdoc only shows a field's visibility in the item declaration, so look
+/// for the field there. Tuple fields can't be located by name and are only
+/// documented when public.
+fn field_visibility(signature: &str, name: &str) -> String {
+  if name.parse::<usize>().is_ok() {
+    return "pub".to_string();
+  }
+
+  regex!(r"(?:^|[{,]\s*)(pub(?:\s*\([^)]*\))?\s+)?(?:r#)?([A-Za-z_][A-Za-z0-9_]*)\s*:")
+    .captures_iter(signature)
+    .find(|captures| &captures[2] == name.trim_start_matches("r#"))
+    .map(|captures| {
+      captures
+        .get(1)
+        .map(|visibility| visibility.as_str().replace(' ', ""))
+        .unwrap_or_else(|| "private".to_string())
+    })
+    .unwrap_or_else(|| "pub".to_string())
+}
+
+/// The docblock rustdoc renders right after a section header, skipping over
+/// any stability badges in between.
+fn sibling_docblock(element: ElementRef) -> Option<String> {
+  sibling_docblock_element(element)
+    .map(|docblock| html_to_markdown(&docblock.inner_html()))
+    .filter(|text| !text.is_empty())
+}
+
+fn sibling_docblock_element(element: ElementRef) -> Option<ElementRef> {
+  element
+    .next_siblings()
+    .filter_map(ElementRef::wrap)
+    .find(|sibling| {
+      !sibling.value().classes().any(|class| class == "item-info")
+    })
+    .filter(|sibling| {
+      sibling.value().classes().any(|class| class == "docblock")
+    })
+}
+
+/// Code blocks in a docblock, along with the heading they appear under and
+/// the test attributes rustdoc exposes as classes (`ignore`, `should_panic`,
+/// `compile_fail`, ...).
+fn extract_examples(docblock: ElementRef) -> Vec<Example> {
+  docblock
+    .select(selector!("pre"))
+    .map(|pre| {
+      let wrapper = pre.parent().and_then(ElementRef::wrap).filter(|parent| {
+        parent
+          .value()
+          .classes()
+          .any(|class| class == "example-wrap")
+      });
+
+      let mut attributes = wrapper
+        .iter()
+        .flat_map(|wrapper| wrapper.value().classes())
+        .chain(pre.value().classes())
+        .filter(|class| {
+          !matches!(*class, "example-wrap" | "rust" | "rust-example-rendered")
+            && !class.starts_with("language-")
+        })
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+      attributes.dedup();
+
+      let block = pre
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .take_while(|ancestor| ancestor.id() != docblock.id())
+        .last()
+        .unwrap_or(pre);
+
+      Example {
+        heading: block
+          .prev_siblings()
+          .filter_map(ElementRef::wrap)
+          .find(|sibling| {
+            matches!(sibling.value().name(), "h1" | "h2" | "h3" | "h4" | "h5")
+          })
+          .map(|heading| {
+            html_to_text(heading.inner_html())
+              .trim_start_matches('§')
+              .trim()
+              .to_string()
+          }),
+        language: match code_language(pre) {
+          language if language.is_empty() => "text".to_string(),
+          language => language,
+        },
+        attributes,
+        code: pre.text().collect::<String>().trim_end().to_string(),
+      }
+    })
+    .collect()
+}
+
+fn extract_method_name(signature: &str) -> String {
+  regex!(r"\bfn\s+(r#)?([A-Za-z_][A-Za-z0-9_]*)")
+    .captures(signature)
+    .map(|captures| captures[2].to_string())
+    .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn extract_enum_variants(document: &Html) -> Vec<Variant> {
+  let variant_fields = document
+    .select(selector!(r#"[id^="variant."][id$=".fields"]"#))
+    .filter_map(|element| Some((element.value().attr("id")?, element)))
+    .collect::<HashMap<_, _>>();
+
+  document
+    .select(selector!("div.variants .variant"))
+    .filter_map(|variant_element| {
+      let header = variant_element
+        .select(selector!(".code-header"))
+        .next()
+        .map(|header| html_to_text(header.inner_html()))
+        .filter(|header| !header.is_empty())?;
+
+      let (name, rest) = header
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map(|end| header.split_at(end))
+        .unwrap_or((&header, ""));
+
+      let (payload, discriminant) = match rest.split_once('=') {
+        Some((payload, discriminant)) => {
+          (payload.trim(), Some(discriminant.trim().to_string()))
+        }
+        None => (rest.trim(), None),
+      };
+
+      let fields = variant_fields
+        .get(format!("variant.{name}.fields").as_str())
+        .into_iter()
+        .flat_map(|fields| fields.select(selector!(".sub-variant-field")))
+        .filter_map(|field| {
+          let (field_name, ty) = field
+            .select(selector!("code"))
+            .next()
+            .map(|code| html_to_text(code.inner_html()))?
+            .split_once(':')
+            .map(|(name, ty)| {
+              (name.trim().to_string(), ty.trim().to_string())
+            })?;
+
+          Some(Field {
+            name: field_name,
+            ty,
+            visibility: "pub".to_string(),
+            description: field
+              .select(selector!(".docblock"))
+              .next()
+              .map(|docblock| html_to_markdown(&docblock.inner_html()))
+              .filter(|text| !text.is_empty()),
+          })
+        })
+        .collect::<Vec<Field>>();
+
+      let kind = if !fields.is_empty() {
+        VariantKind::Struct(fields)
+      } else if let Some(tuple) =
+        payload.strip_prefix('(').and_then(|p| p.strip_suffix(')'))
+      {
+        VariantKind::Tuple(
+          split_top_level(tuple, ',')
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        )
+      } else if let Some(named) =
+        payload.strip_prefix('{').and_then(|p| p.strip_suffix('}'))
+      {
+        VariantKind::Struct(
+          split_top_level(named, ',')
+            .into_iter()
+            .filter_map(|field| {
+              let (name, ty) = field.split_once(':')?;
+
+              Some(Field {
+                name: name.trim().to_string(),
+                ty: ty.trim().to_string(),
+                visibility: "pub".to_string(),
+                description: None,
+              })
+            })
+            .collect(),
+        )
+      } else {
+        VariantKind::Unit
+      };
+
+      Some(Variant {
+        name: name.to_string(),
+        kind,
+        discriminant,
+        description: sibling_docblock(variant_element),
+      })
+    })
+    .collect()
+}
+
+/// Stability badges for the page's own item, skipping those rendered for
+/// methods, variants, impls and module listings further down.
+fn extract_stability(document: &Html) -> Stability {
+  let mut stability = document
+    .select(selector!(".item-info"))
+    .find(|info| {
+      !info
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| {
+          ancestor.value().classes().any(|class| {
+            matches!(
+              class,
+              "impl-items" | "methods" | "variants" | "item-table" | "toggle"
+            )
+          })
+        })
+    })
+    .map(stability)
+    .unwrap_or_default();
+
+  if let Some(badge) = document.select(selector!(".main-heading .since")).next()
+  {
+    (stability.since, stability.const_since) = since(badge);
+  }
+
+  stability
+}
+
+/// The Rust versions a `since` badge says an item was stabilized in, and
+/// became usable in const contexts in, from a title such as "Stable since
+/// Rust version 1.0.0, const since 1.39.0". Const stability that is still
+/// pending reads as `unstable`.
+fn since(badge: ElementRef) -> (Option<String>, Option<String>) {
+  let title = badge.value().attr("title").unwrap_or_default();
+
+  let stable = regex!(r"Stable since Rust version ([^,\s]+)")
+    .captures(title)
+    .map(|captures| captures[1].to_string())
+    .or_else(|| {
+      badge
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .next()
+        .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string)
+    });
+
+  let constant = regex!(r"const (?:since ([^,\s]+)|(unstable))")
+    .captures(title)
+    .and_then(|captures| captures.get(1).or(captures.get(2)))
+    .map(|version| version.as_str().to_string());
+
+  (stable, constant)
+}
+
+fn stability(info: ElementRef) -> Stability {
+  let badge = |selector: &Selector| info.select(selector).next();
+
+  let codes = |element: ElementRef| {
+    element
+      .select(selector!("code"))
+      .map(|code| html_to_text(code.inner_html()))
+      .collect::<Vec<_>>()
+  };
+
+  // Code spans stay in backticks, which tells a `tokio_unstable` cfg apart
+  // from prose such as "Unix".
+  let cfg = badge(selector!(".stab.portability")).and_then(|badge| {
+    let text = badge
+      .descendants()
+      .filter_map(|node| {
+        let text = node.value().as_text()?;
+
+        Some(
+          if node
+            .parent()
+            .and_then(ElementRef::wrap)
+            .is_some_and(|parent| parent.value().name() == "code")
+          {
+            format!("`{}`", &**text)
+          } else {
+            text.to_string()
+          },
+        )
+      })
+      .collect::<String>();
+
+    let text = normalize(&text);
+
+    portability_cfg(
+      text
+        .strip_prefix("Available on ")
+        .and_then(|condition| condition.strip_suffix(" only."))
+        .unwrap_or(&text),
+    )
+  });
+
+  Stability {
+    deprecated: badge(selector!(".stab.deprecated")).map(|badge| {
+      let text = html_to_text(badge.inner_html())
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .to_string();
+
+      let (heading, note) = match text.split_once(':') {
+        Some((heading, note)) => (heading, Some(note.trim().to_string())),
+        None => (text.as_str(), None),
+      };
+
+      Deprecation {
+        since: ["Deprecated since ", "Deprecating in "]
+          .iter()
+          .find_map(|prefix| heading.strip_prefix(prefix))
+          .map(|since| since.trim().to_string()),
+        note: note.filter(|note| !note.is_empty()),
+      }
+    }),
+    unstable: badge(selector!(".stab.unstable")).map(|badge| {
+      codes(badge).into_iter().next().unwrap_or_else(|| {
+        html_to_text(badge.inner_html())
+          .trim_start_matches(|c: char| !c.is_alphanumeric())
+          .to_string()
+      })
+    }),
+    required_features: cfg.as_deref().map(cfg_features).unwrap_or_default(),
+    cfg,
+    since: None,
+    const_since: None,
+  }
+}
+
+fn matches_stability(item: &Item, request: &LookupCrateRequest) -> bool {
+  let stability = item.stability();
+
+  !(request.exclude_deprecated == Some(true) && stability.deprecated.is_some())
+    && request
+      .feature
+      .as_ref()
+      .is_none_or(|feature| stability.required_features.contains(feature))
+}
+
+fn extract_module_items(document: &Html) -> Vec<String> {
+  document
+    .select(selector!(".item-table .item-name a, dl.item-table dt a"))
+    .map(|link| html_to_text(link.inner_html()))
+    .filter(|item| !item.is_empty())
+    .collect()
+}
+
+fn top_docblock(document: &Html) -> Option<ElementRef<'_>> {
+  document
+    .select(selector!("details.toggle.top-doc div.docblock"))
+    .next()
+}
+
+fn extract_description(document: &Html) -> Option<String> {
+  top_docblock(document)
+    .map(|element| html_to_markdown(&element.inner_html()))
+    .filter(|text| !text.is_empty())
+}
+
+fn filter_by_item_type(items: Vec<Item>, filter_type: &str) -> Vec<Item> {
+  items
+    .into_iter()
+    .filter(|item| item.kind().name().eq_ignore_ascii_case(filter_type))
+    .collect()
+}
+
+/// Whether `query` appears in an item's name or in the plain text of its
+/// summary. That is all the search index records, so items are matched the
+/// same way whether or not it exists.
+fn matches_query(item: &Item, query: &str) -> bool {
+  let (name, description) = item.search_items();
+
+  let summary = description
+    .as_deref()
+    .and_then(summary)
+    .map(|summary| markdown_to_text(&summary));
+
+  contains_query(name, summary.as_deref(), query)
+}
+
+/// Like `matches_query`, but using the summaries from the search index,
+/// before any page is parsed.
+fn filter_entries_by_query(entries: Vec<Entry>, query: &str) -> Vec<Entry> {
+  entries
+    .into_iter()
+    .filter(|entry| {
+      contains_query(&entry.name, entry.description.as_deref(), query)
+    })
+    .collect()
+}
+
+fn contains_query(name: &str, summary: Option<&str>, query: &str) -> bool {
+  let query = query.to_lowercase();
+
+  iter::once(name)
+    .chain(summary)
+    .any(|text| text.to_lowercase().contains(&query))
+}
+
+pub fn html_to_text(html: String) -> String {
+  [
+    |text: String| regex!(r"<[^>]*>").replace_all(&text, "").into_owned(),
+    |text: String| {
+      [
+        ("&amp;", "&"),
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+      ]
+      .iter()
+      .fold(text, |acc, &(entity, replacement)| {
+        acc.replace(entity, replacement)
+      })
+    },
+    |text: String| regex!(r"\s+").replace_all(&text, " ").trim().to_string(),
+  ]
+  .iter()
+  .fold(html, |text, transform| transform(text))
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::fs, tempfile::TempDir};
+
+  fn initialize(temp_dir: &TempDir) -> String {
+    let doc_path = temp_dir.path().join("target").join("doc");
+    fs::create_dir_all(&doc_path).unwrap();
+    fs::create_dir_all(doc_path.join("crate")).unwrap();
+    doc_path.to_string_lossy().to_string()
+  }
+
+  fn function_html(
+    name: &str,
+    signature: &str,
+    description: Option<&str>,
+  ) -> String {
+    let desc_html = description
+      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
+      .unwrap_or_default();
+
+    format!(
+      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
+      <pre class="rust item-decl"><code>{}</code></pre>
+      {}
+      </body></html>"#,
       name, signature, desc_html
     )
   }
 
-  fn struct_html(
-    name: &str,
-    signature: &str,
-    description: Option<&str>,
-    methods: &[(String, String, Option<String>)],
-  ) -> String {
-    let desc_html = description
-      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
-      .unwrap_or_default();
+  fn struct_html(
+    name: &str,
+    signature: &str,
+    description: Option<&str>,
+    methods: &[(String, String, Option<String>)],
+  ) -> String {
+    let desc_html = description
+      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
+      .unwrap_or_default();
+
+    let methods_html = methods
+      .iter()
+      .map(|(method_name, method_sig, method_desc)| {
+        let section = format!(
+          r#"<section id="method.{}" class="method"><h4 class="code-header">{}</h4></section>"#,
+          method_name, method_sig
+        );
+        match method_desc {
+          Some(d) => format!(
+            r#"<details class="toggle method-toggle" open><summary>{}</summary><div class="docblock"><p>{}</p></div></details>"#,
+            section, d
+          ),
+          None => section,
+        }
+      })
+      .collect::<Vec<_>>()
+      .join("");
+
+    let impl_html = if methods.is_empty() {
+      String::new()
+    } else {
+      format!(
+        r#"<h2 id="implementations" class="section-header">Implementations</h2><div id="implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-{}" class="impl"><h3 class="code-header">impl {}</h3></section></summary><div class="impl-items">{}</div></details></div>"#,
+        name, name, methods_html
+      )
+    };
+
+    format!(
+      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
+      <pre class="rust item-decl"><code>{}</code></pre>
+      {}
+      {}
+      </body></html>"#,
+      name, signature, desc_html, impl_html
+    )
+  }
+
+  fn enum_html(
+    name: &str,
+    signature: &str,
+    description: Option<&str>,
+    variants: &[String],
+  ) -> String {
+    let desc_html = description
+      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
+      .unwrap_or_default();
+
+    let variants_html = variants
+      .iter()
+      .map(|variant| {
+        format!(
+          r#"<div class="variant"><div class="code-header">{}</div></div>"#,
+          variant
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("");
+
+    format!(
+      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
+      <pre class="rust item-decl"><code>{}</code></pre>
+      {}
+      <div class="variants">{}</div>
+      </body></html>"#,
+      name, signature, desc_html, variants_html
+    )
+  }
+
+  fn module_html(
+    name: &str,
+    description: Option<&str>,
+    items: &[String],
+  ) -> String {
+    let desc_html = description
+      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
+      .unwrap_or_default();
+
+    let items_html = items
+      .iter()
+      .map(|item| {
+        format!("<div class=\"item-name\"><a href=\"#\">{}</a></div>", item)
+      })
+      .collect::<Vec<_>>()
+      .join("");
+
+    format!(
+      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
+      <pre class="rust item-decl"><code>mod {}</code></pre>
+      {}
+      <div class="item-table">{}</div>
+      </body></html>"#,
+      name, name, desc_html, items_html
+    )
+  }
+
+  #[test]
+  fn list_crates() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    fs::create_dir_all(temp_dir.path().join("target/doc/crate_a")).unwrap();
+    fs::create_dir_all(temp_dir.path().join("target/doc/crate_b")).unwrap();
+    fs::create_dir_all(temp_dir.path().join("target/doc/crate_c")).unwrap();
+
+    fs::create_dir_all(temp_dir.path().join("target/doc/static.files"))
+      .unwrap();
+
+    let crates = super::list_crates(&doc_path).unwrap();
+
+    assert_eq!(crates, vec!["crate", "crate_a", "crate_b", "crate_c"]);
+  }
+
+  #[test]
+  fn cross_compiled_targets() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    fs::write(
+      temp_dir.path().join("target/doc/crate/fn.host.html"),
+      function_html("host", "pub fn host()", None),
+    )
+    .unwrap();
+
+    let wasm = temp_dir.path().join("target/wasm32-unknown-unknown/doc");
+
+    for name in ["crate", "wasm_only"] {
+      fs::create_dir_all(wasm.join(name)).unwrap();
+    }
+
+    fs::write(
+      wasm.join("crate/fn.wasm.html"),
+      function_html("wasm", "pub fn wasm()", None),
+    )
+    .unwrap();
+
+    fs::write(
+      wasm.join("wasm_only/fn.run.html"),
+      function_html("run", "pub fn run()", None),
+    )
+    .unwrap();
+
+    fs::create_dir_all(temp_dir.path().join("target/debug/deps")).unwrap();
+
+    let crates = list_crate_targets(&doc_path, None).unwrap();
+
+    assert_eq!(
+      format_crate_targets(&crates),
+      "crate (host, wasm32-unknown-unknown)\nwasm_only (wasm32-unknown-unknown)"
+    );
+
+    let cache = Cache::new(&doc_path, true);
+
+    let lookup = |name: &str, target: Option<&str>| {
+      let request = LookupCrateRequest {
+        name: name.to_string(),
+        target: target.map(str::to_string),
+        ..Default::default()
+      };
+
+      let documentation = lookup_crate(&request, &doc_path, &cache).unwrap();
+
+      (
+        documentation.target,
+        documentation
+          .items
+          .iter()
+          .map(|item| item.path().to_string())
+          .collect::<Vec<_>>(),
+      )
+    };
+
+    assert_eq!(lookup("crate", None), (None, vec!["crate::host".into()]));
+
+    assert_eq!(
+      lookup("crate", Some("wasm32-unknown-unknown")),
+      (
+        Some("wasm32-unknown-unknown".into()),
+        vec!["crate::wasm".into()]
+      )
+    );
+
+    assert_eq!(
+      lookup("crate", Some("host")),
+      (None, vec!["crate::host".into()])
+    );
+
+    assert_eq!(
+      lookup("wasm_only", None),
+      (
+        Some("wasm32-unknown-unknown".into()),
+        vec!["wasm_only::run".into()]
+      )
+    );
+
+    assert!(
+      lookup_crate(
+        &LookupCrateRequest {
+          name: "crate".to_string(),
+          target: Some("thumbv7em-none-eabihf".to_string()),
+          ..Default::default()
+        },
+        &doc_path,
+        &cache,
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn standard_library_docs() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let std_path = temp_dir.path().join("sysroot/share/doc/rust/html");
+
+    for name in ["core", "std", "rustc_private"] {
+      fs::create_dir_all(std_path.join(name)).unwrap();
+    }
+
+    fs::write(
+      std_path.join("std/fn.swap.html"),
+      function_html("swap", "pub const fn swap<T>(x: &mut T, y: &mut T)", None)
+        .replace(
+          "<pre",
+          r#"<div class="main-heading"><h1>Function <span>swap</span></h1><span class="sub-heading"><span class="since" title="Stable since Rust version 1.0.0, const since 1.85.0">1.0.0 (const: 1.85.0)</span></span></div><pre"#,
+        ),
+    )
+    .unwrap();
+
+    let std_path = std_path.to_string_lossy().to_string();
+
+    assert_eq!(
+      format_crate_targets(
+        &list_crate_targets(&doc_path, Some(&std_path)).unwrap()
+      ),
+      "core\ncrate\nstd"
+    );
+
+    let request = LookupCrateRequest {
+      name: "std".to_string(),
+      ..Default::default()
+    };
+
+    let documentation =
+      lookup_crate(&request, &std_path, &Cache::new(&std_path, false)).unwrap();
+
+    let stability = documentation.items[0].stability();
+
+    assert_eq!(
+      (stability.since.as_deref(), stability.const_since.as_deref()),
+      (Some("1.0.0"), Some("1.85.0"))
+    );
+  }
+
+  #[test]
+  fn diff_snapshot_against_regenerated_docs() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let write = |name: &str, html: String| {
+      fs::write(crate_path.join(name), html).unwrap();
+    };
+
+    let config =
+      |signature: &str, fields: &[&str], methods: &[(&str, &str)]| {
+        struct_html(
+          "Config",
+          signature,
+          None,
+          &methods
+            .iter()
+            .map(|(name, signature)| {
+              (name.to_string(), signature.to_string(), None)
+            })
+            .collect::<Vec<_>>(),
+        )
+        .replace(
+          "</body>",
+          &format!(
+            "{}</body>",
+            fields
+              .iter()
+              .map(|field| format!(
+                r#"<span class="structfield"><code>{field}</code></span>"#
+              ))
+              .collect::<String>()
+          ),
+        )
+      };
+
+    write("fn.kept.html", function_html("kept", "pub fn kept()", None));
+    write(
+      "fn.removed.html",
+      function_html("removed", "pub fn removed()", None),
+    );
+
+    write(
+      "struct.Config.html",
+      config(
+        "pub struct Config { pub a: u32 }",
+        &["a: u32"],
+        &[("get", "pub fn get(&amp;self) -&gt; u32")],
+      ),
+    );
+
+    write(
+      "enum.Mode.html",
+      enum_html("Mode", "pub enum Mode", None, &["A".into(), "B(u8)".into()]),
+    );
+
+    write(
+      "struct.Pair.html",
+      config("pub struct Pair(pub u32);", &["0: u32"], &[])
+        .replace("Config", "Pair"),
+    );
+
+    let cache = Cache::new(&doc_path, false);
+
+    let snapshots = temp_dir.path().join("snapshots");
+
+    let request = SaveSnapshotRequest {
+      name: "before".to_string(),
+      crates: None,
+    };
+
+    assert_eq!(
+      save_snapshot(&request, &doc_path, &snapshots, &cache).unwrap(),
+      vec!["crate"]
+    );
+
+    assert!(
+      save_snapshot(
+        &SaveSnapshotRequest {
+          name: "../escape".to_string(),
+          crates: None,
+        },
+        &doc_path,
+        &snapshots,
+        &cache
+      )
+      .is_err()
+    );
+
+    fs::remove_file(crate_path.join("fn.removed.html")).unwrap();
+
+    write(
+      "fn.kept.html",
+      function_html("kept", "pub fn kept(x: u32)", None),
+    );
+    write(
+      "fn.added.html",
+      function_html("added", "pub fn added()", None),
+    );
+
+    write(
+      "struct.Config.html",
+      config(
+        "pub struct Config { pub a: u32, pub b: u32 }",
+        &["a: u32", "b: u32"],
+        &[
+          ("get", "pub fn get(&amp;self) -&gt; u64"),
+          ("set", "pub fn set(&amp;mut self)"),
+        ],
+      ),
+    );
+
+    write(
+      "enum.Mode.html",
+      enum_html(
+        "Mode",
+        "pub enum Mode",
+        None,
+        &["A".into(), "B(u8)".into(), "C".into()],
+      ),
+    );
+
+    write(
+      "struct.Pair.html",
+      config("pub struct Pair(pub u64);", &["0: u64"], &[])
+        .replace("Config", "Pair"),
+    );
+
+    let request = DiffDocsRequest {
+      old: "before".to_string(),
+      ..Default::default()
+    };
+
+    let diffs = diff_docs(&request, &doc_path, &snapshots, &cache).unwrap();
+
+    assert_eq!(diffs.len(), 1);
+
+    assert_eq!(diffs[0].impact, SemverImpact::Major);
+
+    use crate::api_change::ChangeKind::*;
+
+    assert_eq!(
+      diffs[0]
+        .changes
+        .iter()
+        .map(|change| (
+          change.path.as_str(),
+          change.item_type.as_str(),
+          change.change,
+          change.impact,
+        ))
+        .collect::<Vec<_>>(),
+      vec![
+        ("crate::Config::b", "field", Added, SemverImpact::Major),
+        ("crate::Config::get", "method", Changed, SemverImpact::Major),
+        ("crate::Config::set", "method", Added, SemverImpact::Minor),
+        ("crate::Mode::C", "variant", Added, SemverImpact::Major),
+        ("crate::Pair::0", "field", Changed, SemverImpact::Major),
+        ("crate::added", "function", Added, SemverImpact::Minor),
+        ("crate::kept", "function", Changed, SemverImpact::Major),
+        ("crate::removed", "function", Removed, SemverImpact::Major),
+      ]
+    );
+
+    assert_eq!(diffs[0].changes[6].old.as_deref(), Some("pub fn kept()"),);
+
+    assert_eq!(
+      diffs[0].changes[6].new.as_deref(),
+      Some("pub fn kept(x: u32)"),
+    );
+
+    write(
+      "enum.Mode.html",
+      enum_html(
+        "Mode",
+        "#[non_exhaustive]\npub enum Mode",
+        None,
+        &["A".into(), "B(u8)".into()],
+      ),
+    );
+
+    let snapshot = |name: &str| {
+      save_snapshot(
+        &SaveSnapshotRequest {
+          name: name.to_string(),
+          crates: Some(vec!["crate".to_string()]),
+        },
+        &doc_path,
+        &snapshots,
+        &cache,
+      )
+      .unwrap();
+    };
+
+    snapshot("exhaustive");
+
+    write(
+      "enum.Mode.html",
+      enum_html(
+        "Mode",
+        "#[non_exhaustive]\npub enum Mode",
+        None,
+        &["A".into(), "B(u8)".into(), "C".into()],
+      ),
+    );
+
+    snapshot("after");
+
+    let diffs = diff_docs(
+      &DiffDocsRequest {
+        old: "exhaustive".to_string(),
+        new: Some("after".to_string()),
+        name: Some("crate".to_string()),
+      },
+      &doc_path,
+      &snapshots,
+      &cache,
+    )
+    .unwrap();
+
+    assert_eq!(diffs[0].impact, SemverImpact::Minor);
+
+    assert!(Cache::new(&doc_path, true).clear().is_ok());
+
+    assert!(
+      diff_docs(
+        &DiffDocsRequest {
+          old: "missing".to_string(),
+          ..Default::default()
+        },
+        &doc_path,
+        &snapshots,
+        &cache,
+      )
+      .is_err()
+    );
+
+    assert!(
+      diff_docs(
+        &DiffDocsRequest {
+          old: "before".to_string(),
+          new: Some("after".to_string()),
+          name: None,
+        },
+        &doc_path,
+        &snapshots,
+        &cache,
+      )
+      .is_ok()
+    );
+  }
+
+  #[test]
+  fn changed_crates() {
+    let root = Path::new("/project/target/doc");
+
+    let paths = [
+      "crate_a/fn.add.html",
+      "crate_a/sync/struct.Mutex.html",
+      "crate_b",
+      "crate_c.json",
+      "crate_d/index.html",
+      ".crates-mcp/crate_a.json",
+      "search-index.js",
+      "static.files/main.js",
+      "src/crate_a/lib.rs.html",
+      "trait.impl/core/clone/trait.Clone.js",
+    ]
+    .iter()
+    .map(|path| root.join(path))
+    .chain([PathBuf::from("/elsewhere/crate_e/index.html")])
+    .collect::<Vec<_>>();
+
+    assert_eq!(
+      super::changed_crates(root, &paths),
+      BTreeSet::from(
+        ["crate_a", "crate_b", "crate_c", "crate_d"].map(String::from)
+      )
+    );
+  }
+
+  #[test]
+  fn parse_function() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let function_html = function_html(
+      "add",
+      "pub fn add(a: i32, b: i32) -> i32",
+      Some("Adds two numbers together."),
+    );
+
+    fs::write(crate_path.join("fn.add.html"), function_html).unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result.items,
+      vec![Item::Function {
+        name: "add".to_string(),
+        path: "crate::add".to_string(),
+        signature: "pub fn add(a: i32, b: i32) -> i32".to_string(),
+        parsed_signature: FunctionSignature::parse(
+          "pub fn add(a: i32, b: i32) -> i32"
+        ),
+        description: Some("Adds two numbers together.".to_string()),
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
+  }
+
+  #[test]
+  fn parse_struct_with_methods() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let methods = vec![
+      (
+        "new".to_string(),
+        "fn new() -> Self".to_string(),
+        Some("Creates a new instance.".to_string()),
+      ),
+      (
+        "get_value".to_string(),
+        "fn get_value(&self) -> i32".to_string(),
+        None,
+      ),
+    ];
+
+    let struct_html = struct_html(
+      "MyStruct",
+      "pub struct MyStruct { value: i32 }",
+      Some("A simple struct with a value."),
+      &methods,
+    );
+
+    fs::write(crate_path.join("struct.MyStruct.html"), struct_html).unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result.items,
+      vec![Item::Struct {
+        name: "MyStruct".to_string(),
+        path: "crate::MyStruct".to_string(),
+        signature: "pub struct MyStruct { value: i32 }".to_string(),
+        description: Some("A simple struct with a value.".to_string()),
+        fields: Vec::new(),
+        implementations: vec![Implementation {
+          signature: "impl MyStruct".to_string(),
+          trait_name: None,
+          generics: Vec::new(),
+          where_clause: Vec::new(),
+          kind: ImplementationKind::Inherent,
+          methods: vec![
+            Item::Function {
+              name: "new".to_string(),
+              path: "crate::MyStruct::new".to_string(),
+              signature: "fn new() -> Self".to_string(),
+              parsed_signature: FunctionSignature::parse("fn new() -> Self"),
+              description: Some("Creates a new instance.".to_string()),
+              stability: Stability::default(),
+              examples: Vec::new(),
+              links: Vec::new(),
+              aliases: Vec::new(),
+              source: None,
+            },
+            Item::Function {
+              name: "get_value".to_string(),
+              path: "crate::MyStruct::get_value".to_string(),
+              signature: "fn get_value(&self) -> i32".to_string(),
+              parsed_signature: FunctionSignature::parse(
+                "fn get_value(&self) -> i32"
+              ),
+              description: None,
+              stability: Stability::default(),
+              examples: Vec::new(),
+              links: Vec::new(),
+              aliases: Vec::new(),
+              source: None,
+            },
+          ],
+        }],
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
+  }
+
+  #[test]
+  fn parse_implementations() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::write(
+      crate_path.join("struct.Wrapper.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub struct Wrapper&lt;T&gt;(T);</code></pre>
+      <div id="trait-implementations-list">
+        <details class="toggle implementors-toggle" open><summary><section id="impl-Display-for-Wrapper%3CT%3E" class="impl"><h3 class="code-header">impl&lt;T: <a class="trait">Display</a>&gt; <a class="trait">Display</a> for Wrapper&lt;T&gt;</h3></section></summary>
+          <div class="impl-items"><details class="toggle method-toggle" open><summary><section id="method.fmt" class="method trait-impl"><h4 class="code-header">fn fmt(&amp;self, f: &amp;mut Formatter&lt;'_&gt;) -&gt; Result</h4></section></summary><div class="docblock"><p>Formats the value.</p></div></details></div>
+        </details>
+        <section id="impl-Copy-for-Wrapper%3CT%3E" class="impl"><h3 class="code-header">impl&lt;T: Copy&gt; Copy for Wrapper&lt;T&gt;</h3></section>
+      </div>
+      <div id="synthetic-implementations-list">
+        <section id="impl-Send-for-Wrapper%3CT%3E" class="impl"><h3 class="code-header">impl&lt;T&gt; Send for Wrapper&lt;T&gt;<div class="where">where T: Send</div></h3></section>
+      </div>
+      <div id="blanket-implementations-list">
+        <details class="toggle implementors-toggle"><summary><section id="impl-From%3CT%3E-for-T" class="impl"><h3 class="code-header">impl&lt;T&gt; From&lt;T&gt; for T</h3></section></summary>
+          <div class="impl-items"><section id="method.from" class="method trait-impl"><h4 class="code-header">fn from(t: T) -&gt; T</h4></section></div>
+        </details>
+        <section id="impl-ToString-for-T" class="impl"><h3 class="code-header">impl&lt;T: <a class="trait">Display</a> + ?<a class="trait">Sized</a>&gt; <a class="trait">ToString</a> for T<div class="where">where T: <a class="trait">Send</a> + 'static</div></h3></section>
+      </div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Struct {
+      implementations, ..
+    } = &result.items[0]
+    else {
+      panic!("expected a struct, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(
+      implementations
+        .iter()
+        .map(|implementation| (
+          implementation.trait_name.as_deref(),
+          implementation.kind
+        ))
+        .collect::<Vec<_>>(),
+      vec![
+        (Some("Display"), ImplementationKind::Trait),
+        (Some("Copy"), ImplementationKind::Trait),
+        (Some("Send"), ImplementationKind::Auto),
+        (Some("From<T>"), ImplementationKind::Blanket),
+        (Some("ToString"), ImplementationKind::Blanket),
+      ]
+    );
+
+    assert_eq!(
+      implementations[0],
+      Implementation {
+        signature: "impl<T: Display> Display for Wrapper<T>".to_string(),
+        trait_name: Some("Display".to_string()),
+        generics: vec![GenericParameter {
+          name: "T".to_string(),
+          kind: GenericKind::Type,
+          bounds: vec!["Display".to_string()],
+          ty: None,
+          default: None,
+        }],
+        where_clause: Vec::new(),
+        kind: ImplementationKind::Trait,
+        methods: vec![Item::Function {
+          name: "fmt".to_string(),
+          path: "crate::Wrapper::fmt".to_string(),
+          signature: "fn fmt(&self, f: &mut Formatter<'_>) -> Result"
+            .to_string(),
+          parsed_signature: FunctionSignature::parse(
+            "fn fmt(&self, f: &mut Formatter<'_>) -> Result"
+          ),
+          description: Some("Formats the value.".to_string()),
+          stability: Stability::default(),
+          examples: Vec::new(),
+          links: Vec::new(),
+          aliases: Vec::new(),
+          source: None,
+        }],
+      }
+    );
+
+    assert_eq!(implementations[3].methods.len(), 1);
+
+    assert_eq!(
+      (
+        &implementations[4].generics,
+        &implementations[4].where_clause
+      ),
+      (
+        &vec![GenericParameter {
+          name: "T".to_string(),
+          kind: GenericKind::Type,
+          bounds: vec!["Display".to_string(), "?Sized".to_string()],
+          ty: None,
+          default: None,
+        }],
+        &vec![WherePredicate {
+          ty: "T".to_string(),
+          bounds: vec!["Send".to_string(), "'static".to_string()],
+        }]
+      )
+    );
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      exclude_auto_impls: Some(true),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Struct {
+      implementations, ..
+    } = &result.items[0]
+    else {
+      panic!("expected a struct, got {:?}", result.items[0]);
+    };
+
+    assert!(
+      implementations
+        .iter()
+        .all(|implementation| implementation.kind == ImplementationKind::Trait)
+    );
+  }
+
+  #[test]
+  fn parse_struct_fields() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let fields = [
+      ("timeout", "Option&lt;Duration&gt;", Some("How long to wait.")),
+      ("retries", "u32", None),
+      ("limit", "usize", Some("Maximum number of items.")),
+    ]
+    .iter()
+    .map(|(name, ty, description)| {
+      format!(
+        r##"<span id="structfield.{name}" class="structfield section-header"><a href="#structfield.{name}" class="anchor field">§</a><code>{name}: {ty}</code></span>{}"##,
+        description
+          .map(|d| format!(r#"<div class="docblock"><p>{d}</p></div>"#))
+          .unwrap_or_default()
+      )
+    })
+    .collect::<String>();
+
+    fs::write(
+      crate_path.join("struct.Config.html"),
+      struct_html(
+        "Config",
+        "pub struct Config { pub timeout: Option&lt;Duration&gt;, pub(crate) retries: u32, limit: usize, }",
+        None,
+        &[],
+      )
+      .replace("</body>", &format!("{fields}</body>")),
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Struct { fields, .. } = &result.items[0] else {
+      panic!("expected a struct");
+    };
+
+    assert_eq!(
+      fields,
+      &vec![
+        Field {
+          name: "timeout".to_string(),
+          ty: "Option<Duration>".to_string(),
+          visibility: "pub".to_string(),
+          description: Some("How long to wait.".to_string()),
+        },
+        Field {
+          name: "retries".to_string(),
+          ty: "u32".to_string(),
+          visibility: "pub(crate)".to_string(),
+          description: None,
+        },
+        Field {
+          name: "limit".to_string(),
+          ty: "usize".to_string(),
+          visibility: "private".to_string(),
+          description: Some("Maximum number of items.".to_string()),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_enum_with_variants() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let variants = vec!["Success".to_string(), "Error(String)".to_string()];
+
+    let enum_html = enum_html(
+      "Result",
+      "pub enum Result",
+      Some("A type representing either success or failure."),
+      &variants,
+    );
+
+    fs::write(crate_path.join("enum.Result.html"), enum_html).unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result.items,
+      vec![Item::Enum {
+        name: "Result".to_string(),
+        path: "crate::Result".to_string(),
+        signature: "pub enum Result".to_string(),
+        description: Some(
+          "A type representing either success or failure.".to_string()
+        ),
+        variants: vec![
+          Variant {
+            name: "Success".to_string(),
+            kind: VariantKind::Unit,
+            discriminant: None,
+            description: None,
+          },
+          Variant {
+            name: "Error".to_string(),
+            kind: VariantKind::Tuple(vec!["String".to_string()]),
+            discriminant: None,
+            description: None,
+          },
+        ],
+        implementations: Vec::new(),
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
+  }
+
+  #[test]
+  fn parse_enum_variant_details() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::write(
+      crate_path.join("enum.Event.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub enum Event</code></pre>
+      <div class="variants">
+        <section id="variant.Quit" class="variant"><a href="#variant.Quit" class="anchor">§</a><h3 class="code-header">Quit = 1</h3></section>
+        <div class="docblock"><p>The user quit.</p></div>
+        <section id="variant.Resize" class="variant"><a href="#variant.Resize" class="anchor">§</a><h3 class="code-header">Resize(u16, Option&lt;(u16, u16)&gt;)</h3></section>
+        <section id="variant.Key" class="variant"><a href="#variant.Key" class="anchor">§</a><h3 class="code-header">Key</h3></section>
+        <div class="docblock"><p>A key press.</p></div>
+        <div class="sub-variant" id="variant.Key.fields"><h4>Fields</h4>
+          <div class="sub-variant-field"><span id="variant.Key.field.code" class="section-header"><a href="#variant.Key.field.code" class="anchor field">§</a><code>code: char</code></span><div class="docblock"><p>The character.</p></div></div>
+          <div class="sub-variant-field"><span id="variant.Key.field.repeat" class="section-header"><a href="#variant.Key.field.repeat" class="anchor field">§</a><code>repeat: bool</code></span></div>
+        </div>
+      </div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Enum { variants, .. } = &result.items[0] else {
+      panic!("expected an enum, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(
+      variants,
+      &vec![
+        Variant {
+          name: "Quit".to_string(),
+          kind: VariantKind::Unit,
+          discriminant: Some("1".to_string()),
+          description: Some("The user quit.".to_string()),
+        },
+        Variant {
+          name: "Resize".to_string(),
+          kind: VariantKind::Tuple(vec![
+            "u16".to_string(),
+            "Option<(u16, u16)>".to_string()
+          ]),
+          discriminant: None,
+          description: None,
+        },
+        Variant {
+          name: "Key".to_string(),
+          kind: VariantKind::Struct(vec![
+            Field {
+              name: "code".to_string(),
+              ty: "char".to_string(),
+              visibility: "pub".to_string(),
+              description: Some("The character.".to_string()),
+            },
+            Field {
+              name: "repeat".to_string(),
+              ty: "bool".to_string(),
+              visibility: "pub".to_string(),
+              description: None,
+            },
+          ]),
+          discriminant: None,
+          description: Some("A key press.".to_string()),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_trait_members() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::write(
+      crate_path.join("trait.Store.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub trait Store&lt;K&gt;: <a class="trait">Clone</a> + Send <span class="where">where K: Hash</span> {
+    type Value: <a class="trait">Debug</a> + Clone;
+    type Error = <a class="struct">Infallible</a>;
+    const CAPACITY: usize;
+
+    // Required method
+    fn get(&amp;self, key: &amp;K) -&gt; Option&lt;Self::Value&gt;;
+
+    // Provided method
+    fn contains(&amp;self, key: &amp;K) -&gt; bool { ... }
+}</code></pre>
+      <h2 id="required-associated-types" class="section-header">Required Associated Types</h2><div class="methods"><details class="toggle" open><summary><section id="associatedtype.Value" class="method"><h4 class="code-header">type <a class="associatedtype">Value</a>: <a class="trait">Debug</a> + Clone</h4></section></summary><div class="docblock"><p>The stored value.</p></div></details></div>
+      <h2 id="provided-associated-types" class="section-header">Provided Associated Types</h2><div class="methods"><section id="associatedtype.Error" class="method"><h4 class="code-header">type <a class="associatedtype">Error</a> = <a class="struct">Infallible</a></h4></section></div>
+      <h2 id="required-associated-consts" class="section-header">Required Associated Constants</h2><div class="methods"><section id="associatedconstant.CAPACITY" class="method"><h4 class="code-header">const <a class="constant">CAPACITY</a>: usize</h4></section></div>
+      <h2 id="required-methods" class="section-header">Required Methods</h2><div class="methods"><details class="toggle method-toggle" open><summary><section id="tymethod.get" class="method"><h4 class="code-header">fn <a class="fn">get</a>(&amp;self, key: &amp;K) -&gt; Option&lt;Self::Value&gt;</h4></section></summary><div class="docblock"><p>Looks up a key.</p></div></details></div>
+      <h2 id="provided-methods" class="section-header">Provided Methods</h2><div class="methods"><section id="method.contains" class="method"><h4 class="code-header">fn <a class="fn">contains</a>(&amp;self, key: &amp;K) -&gt; bool</h4></section></div>
+      <h2 id="dyn-compatibility" class="section-header">Dyn Compatibility</h2><div class="dyn-compatibility-info"><p>This trait is <b>not</b> dyn compatible.</p></div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Trait {
+      supertraits,
+      required_methods,
+      provided_methods,
+      associated_types,
+      associated_constants,
+      dyn_compatible,
+      ..
+    } = &result.items[0]
+    else {
+      panic!("expected a trait, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(supertraits, &vec!["Clone".to_string(), "Send".to_string()]);
+
+    assert_eq!(
+      required_methods,
+      &vec![Item::Function {
+        name: "get".to_string(),
+        path: "crate::Store::get".to_string(),
+        signature: "fn get(&self, key: &K) -> Option<Self::Value>".to_string(),
+        parsed_signature: FunctionSignature::parse(
+          "fn get(&self, key: &K) -> Option<Self::Value>"
+        ),
+        description: Some("Looks up a key.".to_string()),
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
+
+    assert_eq!(
+      provided_methods,
+      &vec![Item::Function {
+        name: "contains".to_string(),
+        path: "crate::Store::contains".to_string(),
+        signature: "fn contains(&self, key: &K) -> bool".to_string(),
+        parsed_signature: FunctionSignature::parse(
+          "fn contains(&self, key: &K) -> bool"
+        ),
+        description: None,
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
+
+    assert_eq!(
+      associated_types,
+      &vec![
+        AssociatedType {
+          name: "Value".to_string(),
+          signature: "type Value: Debug + Clone".to_string(),
+          bounds: vec!["Debug".to_string(), "Clone".to_string()],
+          default: None,
+          description: Some("The stored value.".to_string()),
+        },
+        AssociatedType {
+          name: "Error".to_string(),
+          signature: "type Error = Infallible".to_string(),
+          bounds: Vec::new(),
+          default: Some("Infallible".to_string()),
+          description: None,
+        },
+      ]
+    );
+
+    assert_eq!(
+      associated_constants,
+      &vec![AssociatedConstant {
+        name: "CAPACITY".to_string(),
+        signature: "const CAPACITY: usize".to_string(),
+        ty: "usize".to_string(),
+        default: None,
+        description: None,
+      }]
+    );
+
+    assert!(!dyn_compatible);
+  }
+
+  #[test]
+  fn find_implementors() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let root = temp_dir.path().join("target/doc");
+
+    fs::write(
+      root.join("crate/trait.Greet.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub trait Greet { }</code></pre>
+      <h2 id="foreign-impls" class="section-header">Implementations on Foreign Types</h2>
+      <details class="toggle implementors-toggle"><summary><section id="impl-Greet-for-String" class="impl"><h3 class="code-header">impl <a class="trait">Greet</a> for String</h3></section></summary><div class="impl-items"></div></details>
+      <h2 id="implementors" class="section-header">Implementors</h2>
+      <div id="implementors-list"><section id="impl-Greet-for-Person" class="impl"><h3 class="code-header">impl <a class="trait" title="trait crate::Greet">Greet</a> for <a class="struct" title="struct crate::Person">Person</a></h3></section><section id="impl-Greet-for-Remote" class="impl"><h3 class="code-header">impl <a class="trait" title="trait crate::Greet">Greet</a> for &amp;<a class="struct" title="struct remote::Remote">Remote</a></h3></section><section id="impl-Greet-for-T" class="impl"><h3 class="code-header">impl&lt;T: <a class="trait" title="trait remote::Marker">Marker</a>&gt; <a class="trait" title="trait crate::Greet">Greet</a> for T</h3></section></div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    fs::create_dir_all(root.join("trait.impl/crate")).unwrap();
+
+    fs::write(
+      root.join("trait.impl/crate/trait.Greet.js"),
+      r#"(function() {
+    const implementors = Object.fromEntries([["other",[["impl&lt;T: <a class=\"trait\">Display</a>&gt; <a class=\"trait\">Greet</a> for <a class=\"struct\">Wrapper</a>&lt;T&gt;<div class=\"where\">where T: Clone</div>",0,["other::Wrapper"]]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()"#,
+    )
+    .unwrap();
+
+    fs::create_dir_all(root.join("implementors/crate")).unwrap();
+
+    fs::write(
+      root.join("implementors/crate/trait.Greet.js"),
+      r#"(function() {var implementors = {
+"legacy":[["impl Greet for Legacy"]]
+};if (window.register_implementors) {window.register_implementors(implementors);}})()"#,
+    )
+    .unwrap();
+
+    let implementor =
+      |crate_name: &str, signature: &str, ty: &str| Implementor {
+        crate_name: crate_name.to_string(),
+        signature: signature.to_string(),
+        ty: ty.to_string(),
+      };
+
+    let target = temp_dir.path().join("target/wasm32-unknown-unknown/doc");
+
+    fs::create_dir_all(target.join("trait.impl/crate")).unwrap();
+
+    fs::write(
+      target.join("trait.impl/crate/trait.Greet.js"),
+      r#"(function() {
+    const implementors = Object.fromEntries([["wasm",[["impl <a class=\"trait\">Greet</a> for <a class=\"struct\">Canvas</a>",0,["wasm::Canvas"]]]],["remote",[["impl <a class=\"trait\">Greet</a> for &amp;<a class=\"struct\">Remote</a>",0,["remote::Remote"]]]]]);
+    window.pending_implementors = implementors;
+})()"#,
+    )
+    .unwrap();
+
+    let std_root = temp_dir.path().join("std");
+
+    fs::create_dir_all(std_root.join("trait.impl/crate")).unwrap();
+
+    fs::write(
+      std_root.join("trait.impl/crate/trait.Greet.js"),
+      r#"(function() {
+    const implementors = Object.fromEntries([["alloc",[["impl <a class=\"trait\">Greet</a> for <a class=\"struct\">Vec</a>",0,["alloc::vec::Vec"]]]]]);
+    window.pending_implementors = implementors;
+})()"#,
+    )
+    .unwrap();
+
+    let expected = vec![
+      implementor("crate", "impl Greet for Person", "Person"),
+      implementor("crate", "impl Greet for String", "String"),
+      implementor("crate", "impl<T: Marker> Greet for T", "T"),
+      implementor("legacy", "impl Greet for Legacy", "Legacy"),
+      implementor(
+        "other",
+        "impl<T: Display> Greet for Wrapper<T>where T: Clone",
+        "Wrapper<T>",
+      ),
+      implementor("remote", "impl Greet for &Remote", "&Remote"),
+      implementor("wasm", "impl Greet for Canvas", "Canvas"),
+    ];
+
+    assert_eq!(
+      super::find_implementors(&doc_path, None, "crate::Greet").unwrap(),
+      expected
+    );
+
+    let std_path = std_root.to_string_lossy();
+
+    assert_eq!(
+      super::find_implementors(&doc_path, Some(&std_path), "crate::Greet")
+        .unwrap(),
+      [
+        vec![implementor("alloc", "impl Greet for Vec", "Vec")],
+        expected,
+      ]
+      .concat()
+    );
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Trait { implementors, .. } = &result.items[0] else {
+      panic!("expected a trait, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(
+      implementors,
+      &vec![
+        implementor("crate", "impl Greet for String", "String"),
+        implementor("crate", "impl Greet for Person", "Person"),
+        implementor("remote", "impl Greet for &Remote", "&Remote"),
+        implementor("crate", "impl<T: Marker> Greet for T", "T"),
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_stability() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::write(
+      crate_path.join("fn.spawn.html"),
+      function_html("spawn", "pub fn spawn()", None).replace(
+        "<pre",
+        r#"<span class="item-info"><div class="stab portability">Available on <strong>crate feature <code>rt</code>, <code>tokio_unstable</code>, and Unix</strong> only.</div></span><pre"#,
+      ),
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("fn.old.html"),
+      function_html("old", "pub fn old()", None).replace(
+        "<pre",
+        r##"<span class="item-info"><div class="stab deprecated"><span class="emoji">👎</span><span>Deprecated since 1.2.0: <p>use <code>spawn</code> instead</p></span></div><div class="stab unstable"><span class="emoji">🔬</span><span>This is a nightly-only experimental API. (<code>old_api</code>&nbsp;<a href="#">#1</a>)</span></div></span><pre"##,
+      ),
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result
+        .items
+        .iter()
+        .map(|item| (item.path(), item.stability().clone()))
+        .collect::<Vec<_>>(),
+      vec![
+        (
+          "crate::old",
+          Stability {
+            deprecated: Some(Deprecation {
+              since: Some("1.2.0".to_string()),
+              note: Some("use spawn instead".to_string()),
+            }),
+            unstable: Some("old_api".to_string()),
+            required_features: Vec::new(),
+            cfg: None,
+            since: None,
+            const_since: None,
+          }
+        ),
+        (
+          "crate::spawn",
+          Stability {
+            deprecated: None,
+            unstable: None,
+            required_features: vec!["rt".to_string()],
+            cfg: Some(
+              r#"all(feature = "rt", tokio_unstable, unix)"#.to_string()
+            ),
+            since: None,
+            const_since: None,
+          }
+        ),
+      ]
+    );
+
+    for request in [
+      LookupCrateRequest {
+        name: "crate".to_string(),
+        exclude_deprecated: Some(true),
+        ..Default::default()
+      },
+      LookupCrateRequest {
+        name: "crate".to_string(),
+        feature: Some("rt".to_string()),
+        ..Default::default()
+      },
+    ] {
+      let result =
+        lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false))
+          .unwrap();
+
+      assert_eq!(
+        result.items.iter().map(Item::path).collect::<Vec<_>>(),
+        vec!["crate::spawn"]
+      );
+    }
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      feature: Some("tokio_unstable".to_string()),
+      ..Default::default()
+    };
+
+    assert!(
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false))
+        .unwrap()
+        .items
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn parse_portability_conditions() {
+    for (condition, cfg) in [
+      ("crate feature `rt`", r#"feature = "rt""#),
+      (
+        "crate features `rt` and `net`",
+        r#"all(feature = "rt", feature = "net")"#,
+      ),
+      (
+        "crate features `a`, `b`, or `c`",
+        r#"any(feature = "a", feature = "b", feature = "c")"#,
+      ),
+      (
+        "crate feature `rt`, `tokio_unstable`, and Unix",
+        r#"all(feature = "rt", tokio_unstable, unix)"#,
+      ),
+      (
+        "target feature `v6k` and non-target feature `thumb-mode`, or AArch64",
+        r#"any(all(target_feature = "v6k", not(target_feature = "thumb-mode")), target_arch = "aarch64")"#,
+      ),
+      (
+        "WebAssembly and `target_family=wasm`",
+        r#"all(target_arch = "wasm32", target_family = "wasm")"#,
+      ),
+      ("non-Windows", "not(windows)"),
+      (
+        "neither Linux nor `miri`",
+        r#"not(any(target_os = "linux", miri))"#,
+      ),
+      (
+        "x86-64 and target feature `sse2`",
+        r#"all(target_arch = "x86_64", target_feature = "sse2")"#,
+      ),
+      (
+        "(crate feature `fs` or Unix) and 64-bit",
+        r#"all(any(feature = "fs", unix), target_pointer_width = "64")"#,
+      ),
+      ("`panic=\"unwind\"`", r#"panic = "unwind""#),
+      (
+        "not (crate feature `std` and Unix)",
+        r#"not(all(feature = "std", unix))"#,
+      ),
+    ] {
+      assert_eq!(
+        portability_cfg(condition).as_deref(),
+        Some(cfg),
+        "{condition}"
+      );
+    }
+
+    assert_eq!(portability_cfg("some future platform"), None);
+
+    assert_eq!(
+      cfg_features(
+        r#"all(feature = "rt", not(feature = "std"), target_feature = "sse2")"#
+      ),
+      vec!["rt"]
+    );
+  }
+
+  #[test]
+  fn parse_examples() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::write(
+      crate_path.join("fn.connect.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub fn connect()</code></pre>
+      <details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock">
+        <p>Opens a connection.</p>
+        <h2 id="examples"><a class="doc-anchor" href="#examples">§</a>Examples</h2>
+        <div class="example-wrap"><pre class="rust rust-example-rendered"><code><span class="kw">let </span>conn = connect();</code></pre></div>
+        <h2 id="panics"><a class="doc-anchor" href="#panics">§</a>Panics</h2>
+        <div class="example-wrap should_panic"><a href="#" class="tooltip" title="This example panics">ⓘ</a><pre class="rust rust-example-rendered should_panic"><code>connect_twice();</code></pre></div>
+        <div class="example-wrap"><pre class="language-toml"><code>[dependencies]</code></pre></div>
+      </div></details>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Function { examples, .. } = &result.items[0] else {
+      panic!("expected a function, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(
+      examples,
+      &vec![
+        Example {
+          heading: Some("Examples".to_string()),
+          language: "rust".to_string(),
+          attributes: Vec::new(),
+          code: "let conn = connect();".to_string(),
+        },
+        Example {
+          heading: Some("Panics".to_string()),
+          language: "rust".to_string(),
+          attributes: vec!["should_panic".to_string()],
+          code: "connect_twice();".to_string(),
+        },
+        Example {
+          heading: Some("Panics".to_string()),
+          language: "toml".to_string(),
+          attributes: Vec::new(),
+          code: "[dependencies]".to_string(),
+        },
+      ]
+    );
+
+    let request = SearchExamplesRequest {
+      query: "connect".to_string(),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      super::search_examples(
+        &request,
+        &doc_path,
+        None,
+        &Cache::new(&doc_path, false)
+      )
+      .unwrap(),
+      vec![ExampleMatch {
+        path: "crate::connect".to_string(),
+        example: examples[0].clone(),
+      }]
+    );
+  }
+
+  #[test]
+  fn search_by_type() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    for (name, declaration) in [
+      ("from_string", "pub fn from_string(s: &amp;str) -&gt; Url"),
+      ("identity", "pub fn identity&lt;T&gt;(value: T) -&gt; T"),
+      ("is_even", "pub fn is_even(n: u32) -&gt; bool"),
+    ] {
+      fs::write(
+        crate_path.join(format!("fn.{name}.html")),
+        format!(
+          r#"<!DOCTYPE html><html><body>
+          <pre class="rust item-decl"><code>{declaration}</code></pre>
+          </body></html>"#
+        ),
+      )
+      .unwrap();
+    }
+
+    fs::write(
+      crate_path.join("struct.Url.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub struct Url { <span class="comment">/* private fields */</span> }</code></pre>
+      <div id="implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-Url" class="impl"><h3 class="code-header">impl Url</h3></section></summary>
+      <div class="impl-items">
+        <section id="method.parse" class="method"><h4 class="code-header">pub fn <a href="#method.parse" class="fn">parse</a>(input: &amp;<a class="primitive" href="https://doc.rust-lang.org/nightly/std/primitive.str.html">str</a>) -&gt; <a class="enum" href="https://doc.rust-lang.org/nightly/core/result/enum.Result.html" title="enum core::result::Result">Result</a>&lt;<a class="struct" href="struct.Url.html" title="struct crate::Url">Url</a>, <a class="struct" href="struct.ParseError.html" title="struct crate::ParseError">ParseError</a>&gt;</h4></section>
+        <section id="method.as_str" class="method"><h4 class="code-header">pub fn <a href="#method.as_str" class="fn">as_str</a>(&amp;self) -&gt; &amp;<a class="primitive" href="https://doc.rust-lang.org/nightly/std/primitive.str.html">str</a></h4></section>
+      </div>
+      </details></div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    let cache = Cache::new(&doc_path, false);
+
+    let search = |query: &str| {
+      super::search_by_type(
+        &SearchByTypeRequest {
+          query: query.to_string(),
+          ..Default::default()
+        },
+        &doc_path,
+        None,
+        &cache,
+      )
+      .unwrap()
+      .into_iter()
+      .map(|type_match| (type_match.path, type_match.exact))
+      .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      search("&str -> Url"),
+      vec![
+        ("crate::from_string".to_string(), true),
+        ("crate::Url::parse".to_string(), false),
+        ("crate::identity".to_string(), false),
+      ]
+    );
+
+    assert_eq!(
+      search("str -> crate::Url"),
+      vec![
+        ("crate::from_string".to_string(), false),
+        ("crate::Url::parse".to_string(), false),
+        ("crate::identity".to_string(), false),
+      ]
+    );
+
+    assert_eq!(
+      search("Url -> &str"),
+      vec![
+        ("crate::Url::as_str".to_string(), false),
+        ("crate::identity".to_string(), false),
+      ]
+    );
+
+    assert_eq!(
+      search("u32 -> bool"),
+      vec![
+        ("crate::is_even".to_string(), true),
+        ("crate::identity".to_string(), false),
+      ]
+    );
+
+    assert_eq!(
+      search("u32"),
+      vec![
+        ("crate::is_even".to_string(), false),
+        ("crate::identity".to_string(), false),
+      ]
+    );
+
+    assert_eq!(
+      search("-> bool"),
+      vec![
+        ("crate::is_even".to_string(), false),
+        ("crate::identity".to_string(), false),
+      ]
+    );
+
+    assert!(
+      super::search_by_type(
+        &SearchByTypeRequest::default(),
+        &doc_path,
+        None,
+        &cache
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn search_across_targets_and_std() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let wasm_path = temp_dir.path().join("target/wasm32-unknown-unknown/doc");
+
+    let std_path = temp_dir.path().join("sysroot/share/doc/rust/html");
+
+    for (root, name, function) in [
+      (Path::new(&doc_path).to_path_buf(), "crate", "host"),
+      (wasm_path.clone(), "crate", "host_copy"),
+      (wasm_path, "web", "wasm"),
+      (std_path.clone(), "core", "builtin"),
+    ] {
+      fs::create_dir_all(root.join(name)).unwrap();
+
+      fs::write(
+        root.join(name).join(format!("fn.{function}.html")),
+        function_html(
+          function,
+          &format!("pub fn {function}(byte: u8) -&gt; bool"),
+          Some(&format!(
+            r#"Checks a byte.</p><div class="example-wrap"><pre class="rust rust-example-rendered"><code>{function}(0);</code></pre></div><p>"#
+          )),
+        ),
+      )
+      .unwrap();
+    }
+
+    let std_path = std_path.to_string_lossy().to_string();
+
+    let cache = Cache::new(&doc_path, false);
+
+    let types = super::search_by_type(
+      &SearchByTypeRequest {
+        query: "u8 -> bool".to_string(),
+        ..Default::default()
+      },
+      &doc_path,
+      Some(&std_path),
+      &cache,
+    )
+    .unwrap();
+
+    assert_eq!(
+      types
+        .iter()
+        .map(|type_match| type_match.path.as_str())
+        .collect::<Vec<_>>(),
+      vec!["web::wasm", "crate::host", "core::builtin"]
+    );
+
+    let examples = super::search_examples(
+      &SearchExamplesRequest {
+        query: "0".to_string(),
+        ..Default::default()
+      },
+      &doc_path,
+      Some(&std_path),
+      &cache,
+    )
+    .unwrap();
+
+    assert_eq!(
+      examples
+        .iter()
+        .map(|example| example.path.as_str())
+        .collect::<Vec<_>>(),
+      vec!["core::builtin", "crate::host", "web::wasm"]
+    );
+  }
+
+  #[test]
+  fn resolve_links() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let module_path = temp_dir.path().join("target/doc/crate/sync");
+
+    fs::create_dir_all(&module_path).unwrap();
+
+    fs::write(
+      module_path.join("struct.Sender.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>pub struct Sender&lt;T&gt; { <span class="comment">/* private fields */</span> }</code></pre>
+      <details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock">
+        <p>The sending half of a <a href="fn.channel.html" title="fn crate::sync::channel"><code>channel</code></a>, see <a href="#method.send">send</a> and <a href="../struct.Config.html#structfield.capacity">capacity</a>.</p>
+        <p>Read the <a href="https://example.com/guide">guide</a>, or <code>[not](a.link)</code>.</p>
+      </div></details>
+      <div id="implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-Sender%3CT%3E" class="impl"><h3 class="code-header">impl&lt;T&gt; Sender&lt;T&gt;</h3></section></summary>
+      <div class="impl-items"><details class="toggle method-toggle" open><summary><section id="method.send" class="method"><h4 class="code-header">fn <a href="#method.send" class="fn">send</a>(&amp;self, t: T) -&gt; <a class="enum" href="../../std/result/enum.Result.html" title="enum std::result::Result">Result</a>&lt;(), <a class="struct" href="struct.SendError.html" title="struct crate::sync::SendError">SendError</a>&lt;T&gt;&gt;</h4></section></summary><div class="docblock"><p>Sends a value, see <a href="index.html">the module</a>.</p></div></details></div>
+      </details></div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Struct {
+      description,
+      links,
+      implementations,
+      ..
+    } = &result.items[0]
+    else {
+      panic!("expected a struct, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(
+      description.as_deref(),
+      Some(
+        "The sending half of a [`channel`](crate::sync::channel), see \
+         [send](crate::sync::Sender::send) and \
+         [capacity](crate::Config::capacity).\n\n\
+         Read the [guide](https://example.com/guide), or `[not](a.link)`."
+      )
+    );
+
+    assert_eq!(
+      links,
+      &vec![
+        "crate::Config::capacity".to_string(),
+        "crate::sync::Sender::send".to_string(),
+        "crate::sync::channel".to_string(),
+      ]
+    );
+
+    let Item::Function {
+      description, links, ..
+    } = &implementations[0].methods[0]
+    else {
+      panic!("expected a method, got {:?}", implementations[0].methods[0]);
+    };
+
+    assert_eq!(
+      description.as_deref(),
+      Some("Sends a value, see [the module](crate::sync).")
+    );
+
+    assert_eq!(
+      links,
+      &vec![
+        "crate::sync".to_string(),
+        "crate::sync::SendError".to_string(),
+        "std::result::Result".to_string(),
+      ]
+    );
+
+    let (markdown, targets) = super::resolve_links(
+      "See [`Vec`], [a map][HashMap] and [`Other`].\n\n```\n[Vec]\n```\n\n[HashMap]: HashMap",
+      |destination| match destination {
+        "`Vec`" => Some("alloc::vec::Vec".to_string()),
+        "HashMap" => Some("std::collections::HashMap".to_string()),
+        _ => None,
+      },
+    );
+
+    assert_eq!(
+      markdown,
+      "See [`Vec`](alloc::vec::Vec), [a map](std::collections::HashMap) and \
+       [`Other`].\n\n```\n[Vec]\n```\n\n\
+       [HashMap]: std::collections::HashMap"
+    );
+
+    assert_eq!(
+      targets,
+      vec![
+        "alloc::vec::Vec".to_string(),
+        "std::collections::HashMap".to_string(),
+        "std::collections::HashMap".to_string(),
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_reexports() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    for module in ["inner", "private"] {
+      fs::create_dir_all(crate_path.join(module)).unwrap();
+    }
+
+    fs::write(
+      crate_path.join("index.html"),
+      r##"<!DOCTYPE html><html><body>
+      <pre class="rust item-decl"><code>mod crate</code></pre>
+      <h2 id="reexports" class="section-header">Re-exports</h2><dl class="item-table reexports">
+      <dt id="reexport.Baz"><code>pub use other::Bar as Baz;</code></dt>
+      <dt><code>pub use crate::<a class="mod" href="inner/index.html" title="mod crate::inner">inner</a>::*;</code></dt>
+      <dt id="reexport.Input"><code>pub use super::inner::<a class="struct" href="inner/struct.Input.html" title="struct crate::inner::Input">Input</a>;</code></dt>
+      </dl>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("inner/index.html"),
+      module_html("inner", None, &[]),
+    )
+    .unwrap();
+
+    let config = |prefix: &str| {
+      format!(
+        r##"<!DOCTYPE html><html><body>
+        <div class="main-heading"><h1>Struct <span class="struct">Config</span></h1><span class="sub-heading"><a class="src" href="{prefix}src/crate/inner.rs.html#3-5">Source</a></span></div>
+        <pre class="rust item-decl"><code>pub struct Config;</code></pre>
+        </body></html>"##
+      )
+    };
+
+    fs::write(
+      crate_path.join("inner/struct.Config.html"),
+      config("../../"),
+    )
+    .unwrap();
+
+    fs::write(crate_path.join("struct.Config.html"), config("../")).unwrap();
+
+    let redirect = |target: &str| {
+      format!(
+        r##"<!DOCTYPE html><html><head><meta http-equiv="refresh" content="0;URL={target}"><title>Redirection</title></head><body><p>Redirecting to <a href="{target}">{target}</a>...</p></body></html>"##
+      )
+    };
+
+    fs::write(
+      crate_path.join("fn.connect.html"),
+      redirect("inner/fn.connect.html"),
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("private/fn.connect.html"),
+      redirect("../inner/fn.connect.html"),
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("inner/fn.connect.html"),
+      function_html("connect", "pub fn connect()", None),
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result
+        .items
+        .iter()
+        .map(|item| item.path())
+        .collect::<Vec<_>>(),
+      vec![
+        "crate",
+        "crate::Config",
+        "crate::inner",
+        "crate::inner::connect"
+      ]
+    );
+
+    let Item::Module { reexports, .. } = &result.items[0] else {
+      panic!("expected a module, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(
+      reexports,
+      &vec![
+        ReExport {
+          path: "crate::Baz".to_string(),
+          original: "other::Bar".to_string(),
+          crate_name: "other".to_string(),
+        },
+        ReExport {
+          path: "crate::*".to_string(),
+          original: "crate::inner::*".to_string(),
+          crate_name: "crate".to_string(),
+        },
+        ReExport {
+          path: "crate::Input".to_string(),
+          original: "crate::inner::Input".to_string(),
+          crate_name: "crate".to_string(),
+        },
+      ]
+    );
+
+    let Item::Struct {
+      aliases, source, ..
+    } = &result.items[1]
+    else {
+      panic!("expected a struct, got {:?}", result.items[1]);
+    };
+
+    assert_eq!(aliases, &vec!["crate::inner::Config".to_string()]);
+
+    assert_eq!(source.as_deref(), Some("crate/inner.rs#3-5"));
+
+    let Item::Function { aliases, .. } = &result.items[3] else {
+      panic!("expected a function, got {:?}", result.items[3]);
+    };
+
+    assert_eq!(aliases, &vec!["crate::connect".to_string()]);
+
+    assert_eq!(
+      super::absolute_path("super::super::Thing", "crate::a::b"),
+      "crate::Thing"
+    );
+  }
+
+  #[test]
+  fn paginate_merged_reexports() {
+    let temp_dir = TempDir::new().unwrap();
 
-    let methods_html = methods
-      .iter()
-      .map(|(_method_name, method_sig, method_desc)| {
-        let method_desc_html = method_desc
-          .as_ref()
-          .map(|d| format!(r#"<div class="docblock"><p>{}</p></div>"#, d))
-          .unwrap_or_default();
-        format!(
-          r#"<div class="toggle method-toggle"><div class="code-header">{}</div>{}</div>"#,
-          method_sig, method_desc_html
-        )
-      })
-      .collect::<Vec<_>>()
-      .join("");
+    let doc_path = initialize(&temp_dir);
 
-    format!(
-      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
-      <pre class="rust item-decl"><code>{}</code></pre>
-      {}
-      <div class="impl-items">{}</div>
-      </body></html>"#,
-      name, signature, desc_html, methods_html
-    )
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::create_dir_all(crate_path.join("a")).unwrap();
+
+    fs::write(crate_path.join("a/index.html"), module_html("a", None, &[]))
+      .unwrap();
+
+    let function = |name: &str, prefix: &str, line: usize| {
+      format!(
+        r##"<!DOCTYPE html><html><body>
+        <div class="main-heading"><h1>Function <span class="fn">{name}</span></h1><span class="sub-heading"><a class="src" href="{prefix}src/crate/lib.rs.html#{line}">Source</a></span></div>
+        <pre class="rust item-decl"><code>pub fn {name}()</code></pre>
+        </body></html>"##
+      )
+    };
+
+    fs::write(crate_path.join("fn.f.html"), function("f", "../", 1)).unwrap();
+
+    fs::write(crate_path.join("a/fn.f.html"), function("f", "../../", 1))
+      .unwrap();
+
+    fs::write(crate_path.join("fn.g.html"), function("g", "../", 2)).unwrap();
+
+    let cache = Cache::new(&doc_path, false);
+
+    let page = |offset: usize, limit: usize| {
+      let request = LookupCrateRequest {
+        name: "crate".to_string(),
+        item_type: Some("function".to_string()),
+        offset: Some(offset),
+        limit: Some(limit),
+        ..Default::default()
+      };
+
+      lookup_crate(&request, &doc_path, &cache)
+        .unwrap()
+        .items
+        .iter()
+        .map(|item| (item.path().to_string(), item.aliases().to_vec()))
+        .collect::<Vec<_>>()
+    };
+
+    let f = ("crate::f".to_string(), vec!["crate::a::f".to_string()]);
+
+    let g = ("crate::g".to_string(), Vec::new());
+
+    assert_eq!(page(0, 2), vec![f.clone(), g.clone()]);
+
+    assert_eq!(page(0, 1), vec![f]);
+
+    assert_eq!(page(1, 1), vec![g]);
+
+    assert_eq!(page(2, 1), Vec::new());
   }
 
-  fn enum_html(
-    name: &str,
-    signature: &str,
-    description: Option<&str>,
-    variants: &[String],
-  ) -> String {
-    let desc_html = description
-      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
-      .unwrap_or_default();
+  #[test]
+  fn get_source() {
+    let temp_dir = TempDir::new().unwrap();
 
-    let variants_html = variants
-      .iter()
-      .map(|variant| {
-        format!(
-          r#"<div class="variant"><div class="code-header">{}</div></div>"#,
-          variant
-        )
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    fs::write(
+      crate_path.join("struct.Counter.html"),
+      r##"<!DOCTYPE html><html><body>
+      <div class="main-heading"><h1>Struct <span class="struct">Counter</span></h1><span class="sub-heading"><a class="src" href="../src/crate/lib.rs.html#1-3">Source</a></span></div>
+      <pre class="rust item-decl"><code>pub struct Counter { /* private fields */ }</code></pre>
+      <h2 id="implementations" class="section-header">Implementations</h2><div id="implementations-list"><details class="toggle implementors-toggle" open><summary><section id="impl-Counter" class="impl"><h3 class="code-header">impl Counter</h3></section></summary><div class="impl-items"><section id="method.increment" class="method"><a class="src rightside" href="../src/crate/lib.rs.html#6-8">Source</a><h4 class="code-header">pub fn <a href="#method.increment" class="fn">increment</a>(&amp;mut self)</h4></section></div></details></div>
+      </body></html>"##,
+    )
+    .unwrap();
+
+    fs::create_dir_all(temp_dir.path().join("target/doc/src/crate")).unwrap();
+
+    fs::write(
+      temp_dir.path().join("target/doc/src/crate/lib.rs.html"),
+      r##"<!DOCTYPE html><html><body><main><div class="example-wrap digits-1"><pre class="rust"><code><a href=#1 id=1 data-nosnippet>1</a><span class="kw">pub struct </span>Counter {
+<a href=#2 id=2 data-nosnippet>2</a>    count: u64,
+<a href=#3 id=3 data-nosnippet>3</a>}
+<a href=#4 id=4 data-nosnippet>4</a>
+<a href=#5 id=5 data-nosnippet>5</a><span class="kw">impl </span>Counter {
+<a href=#6 id=6 data-nosnippet>6</a>    <span class="kw">pub fn </span>increment(<span class="kw-2">&amp;mut </span><span class="self">self</span>) {
+<a href=#7 id=7 data-nosnippet>7</a>        <span class="self">self</span>.count += <span class="number">1</span>;
+<a href=#8 id=8 data-nosnippet>8</a>    }
+<a href=#9 id=9 data-nosnippet>9</a>}</code></pre></div></main></body></html>"##,
+    )
+    .unwrap();
+
+    let cache = Cache::new(&doc_path, false);
+
+    let source = |request: GetSourceRequest| {
+      super::get_source(&request, &doc_path, None, &cache).unwrap()
+    };
+
+    assert_eq!(
+      source(GetSourceRequest {
+        path: "crate::Counter".to_string(),
+        ..Default::default()
+      }),
+      SourceCode {
+        path: "crate::Counter".to_string(),
+        file: "crate/lib.rs".to_string(),
+        start_line: 1,
+        end_line: 3,
+        code: "pub struct Counter {\n    count: u64,\n}".to_string(),
+      }
+    );
+
+    assert_eq!(
+      source(GetSourceRequest {
+        path: "crate::Counter::increment".to_string(),
+        ..Default::default()
       })
-      .collect::<Vec<_>>()
-      .join("");
+      .code,
+      "    pub fn increment(&mut self) {\n        self.count += 1;\n    }"
+    );
 
-    format!(
-      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
-      <pre class="rust item-decl"><code>{}</code></pre>
-      {}
-      <div class="variants">{}</div>
-      </body></html>"#,
-      name, signature, desc_html, variants_html
+    let whole_file = source(GetSourceRequest {
+      path: "crate::Counter".to_string(),
+      whole_file: Some(true),
+      ..Default::default()
+    });
+
+    assert_eq!((whole_file.start_line, whole_file.end_line), (1, 9));
+
+    let range = source(GetSourceRequest {
+      path: "crate::Counter".to_string(),
+      start_line: Some(5),
+      end_line: Some(50),
+      ..Default::default()
+    });
+
+    assert_eq!((range.start_line, range.end_line), (5, 9));
+
+    assert!(
+      super::get_source(
+        &GetSourceRequest {
+          path: "crate::Counter::decrement".to_string(),
+          ..Default::default()
+        },
+        &doc_path,
+        None,
+        &cache,
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn get_source_from_json_spans() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let json = serde_json::json!({
+      "root": 0,
+      "crate_version": "0.1.0",
+      "format_version": 39,
+      "index": {
+        "0": {
+          "id": 0,
+          "crate_id": 0,
+          "name": "crate",
+          "visibility": "public",
+          "inner": { "module": { "is_crate": true, "items": [1] } }
+        },
+        "1": {
+          "id": 1,
+          "crate_id": 0,
+          "name": "answer",
+          "visibility": "public",
+          "span": {
+            "filename": "src/lib.rs",
+            "begin": [2, 1],
+            "end": [4, 2]
+          },
+          "inner": {
+            "function": {
+              "sig": { "inputs": [], "output": { "primitive": "u32" } },
+              "generics": { "params": [], "where_predicates": [] },
+              "header": {
+                "is_const": false,
+                "is_unsafe": false,
+                "is_async": false,
+                "abi": "Rust"
+              }
+            }
+          }
+        }
+      },
+      "paths": {
+        "0": { "crate_id": 0, "path": ["crate"], "kind": "module" },
+        "1": { "crate_id": 0, "path": ["crate", "answer"], "kind": "function" }
+      },
+      "external_crates": {}
+    });
+
+    fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+
+    fs::write(
+      temp_dir.path().join("src/lib.rs"),
+      "// The answer.\npub fn answer() -> u32 {\n  42\n}\n",
     )
+    .unwrap();
+
+    let request = GetSourceRequest {
+      path: "crate::answer".to_string(),
+      ..Default::default()
+    };
+
+    let code = "pub fn answer() -> u32 {\n  42\n}";
+
+    // Spans are relative to the workspace root, which is the directory
+    // holding `target/` unless discovery found another.
+    for (doc_path, workspace_root) in [
+      (temp_dir.path().join("target/doc"), None),
+      (temp_dir.path().join("out/doc"), Some(temp_dir.path())),
+    ] {
+      fs::create_dir_all(&doc_path).unwrap();
+
+      fs::write(doc_path.join("crate.json"), json.to_string()).unwrap();
+
+      let doc_path = doc_path.to_string_lossy().into_owned();
+
+      let cache = Cache::new(&doc_path, false);
+
+      assert_eq!(
+        super::get_source(&request, &doc_path, workspace_root, &cache)
+          .unwrap()
+          .code,
+        code
+      );
+
+      if workspace_root.is_some() {
+        assert!(super::get_source(&request, &doc_path, None, &cache).is_err());
+      }
+    }
   }
 
-  fn module_html(
-    name: &str,
-    description: Option<&str>,
-    items: &[String],
-  ) -> String {
-    let desc_html = description
-      .map(|d| format!(r#"<details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>{}</p></div></details>"#, d))
-      .unwrap_or_default();
+  #[test]
+  fn parse_module() {
+    let temp_dir = TempDir::new().unwrap();
 
-    let items_html = items
-      .iter()
-      .map(|item| {
-        format!("<div class=\"item-name\"><a href=\"#\">{}</a></div>", item)
-      })
-      .collect::<Vec<_>>()
-      .join("");
+    let doc_path = initialize(&temp_dir);
 
-    format!(
-      r#"<!DOCTYPE html><html><head><title>{}</title></head><body>
-      <pre class="rust item-decl"><code>mod {}</code></pre>
-      {}
-      <div class="item-table">{}</div>
-      </body></html>"#,
-      name, name, desc_html, items_html
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let items = vec!["function_a".to_string(), "struct_b".to_string()];
+
+    let module_html =
+      module_html("utils", Some("Utility functions and types."), &items);
+
+    fs::create_dir_all(crate_path.join("utils")).unwrap();
+
+    fs::write(crate_path.join("utils/index.html"), module_html).unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result.items,
+      vec![Item::Module {
+        name: "utils".to_string(),
+        path: "crate::utils".to_string(),
+        description: Some("Utility functions and types.".to_string()),
+        items: vec!["function_a".to_string(), "struct_b".to_string()],
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+        reexports: Vec::new(),
+      }]
+    );
+  }
+
+  #[test]
+  fn parse_all_item_kinds() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let pages = [
+      (
+        "union.Bits.html",
+        "pub union Bits { pub int: u32, pub float: f32 }",
+      ),
+      ("static.COUNTER.html", "pub static COUNTER: AtomicUsize"),
+      (
+        "traitalias.Sendable.html",
+        "pub trait Sendable = Send + Sync;",
+      ),
+      ("attr.main.html", "#[main]"),
+      (
+        "derive.Serialize.html",
+        "#[derive(Serialize)]\n{\n    // Attributes available to this derive:\n    #[serde]\n}",
+      ),
+      ("foreigntype.Opaque.html", "pub type Opaque;"),
+      ("unknown.Thing.html", "pub thing Thing"),
+    ];
+
+    for (file, signature) in pages {
+      fs::write(crate_path.join(file), function_html("", signature, None))
+        .unwrap();
+    }
+
+    fs::write(
+      crate_path.join("primitive.u8.html"),
+      r#"<html><body><details class="toggle top-doc" open><div class="docblock"><p>The 8-bit unsigned integer type.</p></div></details></body></html>"#,
     )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("keyword.async.html"),
+      r#"<html><body><details class="toggle top-doc" open><div class="docblock"><p>Returns a Future.</p></div></details></body></html>"#,
+    )
+    .unwrap();
+
+    let kinds = |item_type: Option<&str>| {
+      let request = LookupCrateRequest {
+        name: "crate".to_string(),
+        item_type: item_type.map(str::to_string),
+        ..Default::default()
+      };
+
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false))
+        .unwrap()
+        .items
+        .iter()
+        .map(|item| item.kind())
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      kinds(None),
+      vec![
+        ItemKind::Union,
+        ItemKind::Static,
+        ItemKind::ForeignType,
+        ItemKind::TraitAlias,
+        ItemKind::DeriveMacro,
+        ItemKind::Keyword,
+        ItemKind::AttributeMacro,
+        ItemKind::Primitive,
+      ]
+    );
+
+    assert_eq!(kinds(Some("derive_macro")), vec![ItemKind::DeriveMacro]);
+
+    assert_eq!(
+      kinds(Some("attribute_macro")),
+      vec![ItemKind::AttributeMacro]
+    );
+
+    assert!(kinds(Some("module")).is_empty());
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      item_type: Some("derive_macro".to_string()),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false))
+        .unwrap()
+        .items,
+      vec![Item::DeriveMacro {
+        name: "Serialize".to_string(),
+        path: "crate::Serialize".to_string(),
+        signature: "#[derive(Serialize)] { // Attributes available to this derive: #[serde] }".to_string(),
+        description: None,
+        helper_attributes: vec!["serde".to_string()],
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
   }
 
   #[test]
-  fn list_crates() {
+  fn filter_by_item_type() {
     let temp_dir = TempDir::new().unwrap();
 
-    let doc_path = initialize(&temp_dir);
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    let function_html =
+      function_html("add", "pub fn add(a: i32, b: i32) -> i32", None);
+
+    let struct_html = struct_html("MyStruct", "pub struct MyStruct", None, &[]);
 
-    fs::create_dir_all(temp_dir.path().join("target/doc/crate_a")).unwrap();
-    fs::create_dir_all(temp_dir.path().join("target/doc/crate_b")).unwrap();
-    fs::create_dir_all(temp_dir.path().join("target/doc/crate_c")).unwrap();
+    fs::write(crate_path.join("fn.add.html"), function_html).unwrap();
+    fs::write(crate_path.join("struct.MyStruct.html"), struct_html).unwrap();
 
-    fs::create_dir_all(temp_dir.path().join("target/doc/static.files"))
-      .unwrap();
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      item_type: Some("function".to_string()),
+      ..Default::default()
+    };
 
-    let crates = super::list_crates(&doc_path).unwrap();
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
 
-    assert_eq!(crates, vec!["crate", "crate_a", "crate_b", "crate_c"]);
+    assert_eq!(
+      result.items,
+      vec![Item::Function {
+        name: "add".to_string(),
+        path: "crate::add".to_string(),
+        signature: "pub fn add(a: i32, b: i32) -> i32".to_string(),
+        parsed_signature: FunctionSignature::parse(
+          "pub fn add(a: i32, b: i32) -> i32"
+        ),
+        description: None,
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
+      }]
+    );
   }
 
   #[test]
-  fn parse_function() {
+  fn filter_by_query() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
     let crate_path = temp_dir.path().join("target/doc/crate");
 
-    let function_html = function_html(
+    let function1_html = function_html(
       "add",
       "pub fn add(a: i32, b: i32) -> i32",
-      Some("Adds two numbers together."),
+      Some("Adds numbers"),
     );
 
-    fs::write(crate_path.join("fn.add.html"), function_html).unwrap();
+    let function2_html = function_html(
+      "subtract",
+      "pub fn subtract(a: i32, b: i32) -> i32",
+      Some("Subtracts numbers</p><p>The inverse of adding them"),
+    );
+
+    fs::write(crate_path.join("fn.add.html"), function1_html).unwrap();
+    fs::write(crate_path.join("fn.subtract.html"), function2_html).unwrap();
+
+    // Only the summary is searched, as when the search index has it.
 
     let request = LookupCrateRequest {
       name: "crate".to_string(),
-      item_type: None,
-      query: None,
-      limit: None,
-      offset: None,
+      query: Some("add".to_string()),
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
 
     assert_eq!(
       result.items,
       vec![Item::Function {
         name: "add".to_string(),
+        path: "crate::add".to_string(),
         signature: "pub fn add(a: i32, b: i32) -> i32".to_string(),
-        description: Some("Adds two numbers together.".to_string()),
+        parsed_signature: FunctionSignature::parse(
+          "pub fn add(a: i32, b: i32) -> i32"
+        ),
+        description: Some("Adds numbers".to_string()),
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
       }]
     );
   }
 
   #[test]
-  fn parse_struct_with_methods() {
+  fn pagination() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
     let crate_path = temp_dir.path().join("target/doc/crate");
 
-    let methods = vec![
-      (
-        "new".to_string(),
-        "fn new() -> Self".to_string(),
-        Some("Creates a new instance.".to_string()),
-      ),
-      (
-        "get_value".to_string(),
-        "fn get_value(&self) -> i32".to_string(),
+    for i in 0..5 {
+      let function_html = function_html(
+        &format!("func_{}", i),
+        &format!("pub fn func_{}()", i),
         None,
-      ),
-    ];
-
-    let struct_html = struct_html(
-      "MyStruct",
-      "pub struct MyStruct { value: i32 }",
-      Some("A simple struct with a value."),
-      &methods,
-    );
+      );
 
-    fs::write(crate_path.join("struct.MyStruct.html"), struct_html).unwrap();
+      fs::write(
+        crate_path.join(format!("fn.func_{}.html", i)),
+        function_html,
+      )
+      .unwrap();
+    }
 
     let request = LookupCrateRequest {
       name: "crate".to_string(),
-      item_type: None,
-      query: None,
-      limit: None,
-      offset: None,
+      limit: Some(2),
+      offset: Some(1),
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(result.items.len(), 2);
+  }
+
+  #[test]
+  fn extract_item_name() {
+    assert_eq!(super::extract_item_name("fn.add.html").unwrap(), "add");
 
     assert_eq!(
-      result.items,
-      vec![Item::Struct {
-        name: "MyStruct".to_string(),
-        signature: "pub struct MyStruct { value: i32 }".to_string(),
-        description: Some("A simple struct with a value.".to_string()),
-        methods: vec![
-          Item::Function {
-            name: "new".to_string(),
-            signature: "fn new() -> Self".to_string(),
-            description: Some("Creates a new instance.".to_string()),
+      super::extract_item_name("struct.MyStruct.html").unwrap(),
+      "MyStruct"
+    );
+
+    assert_eq!(
+      super::extract_item_name("enum.Result.html").unwrap(),
+      "Result"
+    );
+
+    assert_eq!(
+      super::extract_item_name("module.index.html").unwrap(),
+      "index"
+    );
+
+    assert!(super::extract_item_name("invalid").is_err());
+
+    assert!(super::extract_item_name("fn.add.txt").is_err());
+  }
+
+  #[test]
+  fn extract_method_name() {
+    assert_eq!(super::extract_method_name("fn new() -> Self"), "new");
+
+    assert_eq!(
+      super::extract_method_name("fn get_value(&self) -> i32"),
+      "get_value"
+    );
+
+    assert_eq!(
+      super::extract_method_name(
+        "fn complex_method<T>(self, param: T) where T: Clone"
+      ),
+      "complex_method"
+    );
+
+    assert_eq!(
+      super::extract_method_name(
+        "pub const unsafe fn get_unchecked(&self, index: usize) -> &T"
+      ),
+      "get_unchecked"
+    );
+
+    assert_eq!(super::extract_method_name("pub fn r#try(self)"), "try");
+
+    assert_eq!(super::extract_method_name("invalid signature"), "unknown");
+  }
+
+  #[test]
+  fn parse_function_signature() {
+    assert_eq!(
+      FunctionSignature::parse(
+        "#[must_use]\npub const unsafe fn get_unchecked<'a, T: Copy + 'a, const N: usize = 4>(&'a mut self, index: usize, f: impl Fn(&T) -> bool) -> Option<&'a [T; N]>where\n    T: Send + Sync,\n    for<'b> &'b T: Into<u64>,"
+      ),
+      Some(FunctionSignature {
+        name: "get_unchecked".to_string(),
+        is_async: false,
+        is_const: true,
+        is_unsafe: true,
+        abi: None,
+        generics: vec![
+          GenericParameter {
+            name: "'a".to_string(),
+            kind: GenericKind::Lifetime,
+            bounds: Vec::new(),
+            ty: None,
+            default: None,
           },
-          Item::Function {
-            name: "get_value".to_string(),
-            signature: "fn get_value(&self) -> i32".to_string(),
-            description: None,
+          GenericParameter {
+            name: "T".to_string(),
+            kind: GenericKind::Type,
+            bounds: vec!["Copy".to_string(), "'a".to_string()],
+            ty: None,
+            default: None,
+          },
+          GenericParameter {
+            name: "N".to_string(),
+            kind: GenericKind::Const,
+            bounds: Vec::new(),
+            ty: Some("usize".to_string()),
+            default: Some("4".to_string()),
+          },
+        ],
+        receiver: Some(Receiver::RefMut),
+        parameters: vec![
+          Parameter {
+            name: "index".to_string(),
+            ty: "usize".to_string(),
+          },
+          Parameter {
+            name: "f".to_string(),
+            ty: "impl Fn(&T) -> bool".to_string(),
           },
         ],
+        return_type: Some("Option<&'a [T; N]>".to_string()),
+        where_clause: vec![
+          WherePredicate {
+            ty: "T".to_string(),
+            bounds: vec!["Send".to_string(), "Sync".to_string()],
+          },
+          WherePredicate {
+            ty: "for<'b> &'b T".to_string(),
+            bounds: vec!["Into<u64>".to_string()],
+          },
+        ],
+      })
+    );
+
+    let parse = |signature: &str| FunctionSignature::parse(signature).unwrap();
+
+    let signature =
+      parse(r#"pub(crate) async extern "system" fn r#try(mut self) -> Self;"#);
+
+    assert_eq!(
+      (
+        signature.name.as_str(),
+        signature.is_async,
+        signature.abi.as_deref(),
+        signature.receiver,
+        signature.return_type.as_deref(),
+      ),
+      (
+        "try",
+        true,
+        Some("system"),
+        Some(Receiver::Value),
+        Some("Self")
+      )
+    );
+
+    assert_eq!(
+      parse("fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) { ... }")
+        .receiver,
+      Some(Receiver::Typed("Pin<&mut Self>".to_string()))
+    );
+
+    assert_eq!(parse("fn get(&self) -> u8").receiver, Some(Receiver::Ref));
+
+    let signature = parse("pub extern fn callback(data: *const c_void)");
+
+    assert_eq!(
+      (
+        signature.abi.as_deref(),
+        signature.receiver,
+        signature.return_type
+      ),
+      (Some("C"), None, None)
+    );
+
+    assert_eq!(
+      signature.parameters,
+      vec![Parameter {
+        name: "data".to_string(),
+        ty: "*const c_void".to_string(),
+      }]
+    );
+
+    assert_eq!(
+      parse("pub fn parse<T>(s: &str) -> Result<T, T::Err>where T: FromStr")
+        .where_clause,
+      vec![WherePredicate {
+        ty: "T".to_string(),
+        bounds: vec!["FromStr".to_string()],
       }]
     );
+
+    assert_eq!(FunctionSignature::parse("pub struct Foo;"), None);
+    assert_eq!(FunctionSignature::parse("pub static X: u8"), None);
   }
 
   #[test]
-  fn parse_enum_with_variants() {
+  fn html_to_text() {
+    assert_eq!(
+      super::html_to_text("<p>Hello <strong>world</strong></p>".to_string()),
+      "Hello world"
+    );
+
+    assert_eq!(
+      super::html_to_text("&lt;div&gt; &amp; &quot;test&quot;".to_string()),
+      "<div> & \"test\""
+    );
+
+    assert_eq!(
+      super::html_to_text("   Multiple    spaces   ".to_string()),
+      "Multiple spaces"
+    );
+
+    assert_eq!(
+      super::html_to_text("<code>fn test() -&gt; bool</code>".to_string()),
+      "fn test() -> bool"
+    );
+  }
+
+  #[test]
+  fn html_to_markdown() {
+    assert_eq!(
+      super::html_to_markdown(
+        r##"<h2 id="examples"><a class="doc-anchor" href="#examples">§</a>Examples</h2>
+        <p>Use <code>Vec::new</code> or <a href="macro.vec.html"><code>vec!</code></a>, <em>not</em> <strong>this</strong>:</p>
+        <div class="example-wrap ignore"><a href="#" class="tooltip" title="This example is not tested">ⓘ</a><pre class="rust rust-example-rendered"><code><span class="kw">let </span>v = <span class="macro">vec!</span>[<span class="number">1</span>];
+
+<span class="macro">assert_eq!</span>(v.len(), <span class="number">1</span>);</code></pre></div>
+        <ul><li>first</li><li><p>second</p><ol><li>nested</li></ol></li></ul>
+        <table><thead><tr><th>Name</th><th>Value</th></tr></thead><tbody><tr><td><code>a</code></td><td>1</td></tr></tbody></table>"##
+      ),
+      "## Examples
+
+Use `Vec::new` or [`vec!`](macro.vec.html), *not* **this**:
+
+```rust
+let v = vec![1];
+
+assert_eq!(v.len(), 1);
+```
+
+- first
+- second
+  1. nested
+
+| Name | Value |
+| --- | --- |
+| `a` | 1 |"
+    );
+
+    assert_eq!(
+      super::markdown_to_text(
+        "## Examples\n\nSee [`Foo`](struct.Foo.html) **now**.\n\n```rust\nlet x = 1;\n```"
+      ),
+      "Examples See Foo now. let x = 1;"
+    );
+
+    assert_eq!(
+      super::markdown_examples(
+        "Intro.\n\n# Examples\n\n```\n# use foo::Bar;\nlet bar = Bar::new();\n## not hidden\n```\n\n```no_run,edition2021\nserve();\n```\n\n````text\n```\n````"
+      ),
+      vec![
+        Example {
+          heading: Some("Examples".to_string()),
+          language: "rust".to_string(),
+          attributes: Vec::new(),
+          code: "let bar = Bar::new();\n# not hidden".to_string(),
+        },
+        Example {
+          heading: Some("Examples".to_string()),
+          language: "rust".to_string(),
+          attributes: vec!["no_run".to_string(), "edition2021".to_string()],
+          code: "serve();".to_string(),
+        },
+        Example {
+          heading: Some("Examples".to_string()),
+          language: "text".to_string(),
+          attributes: Vec::new(),
+          code: "```".to_string(),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_rustdoc_json() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
-    let crate_path = temp_dir.path().join("target/doc/crate");
+    let json = serde_json::json!({
+      "root": 0,
+      "crate_version": "0.3.1",
+      "format_version": 39,
+      "index": {
+        "0": {
+          "id": 0,
+          "crate_id": 0,
+          "name": "crate",
+          "visibility": "public",
+          "docs": "The crate root.",
+          "inner": { "module": { "is_crate": true, "items": [1, 2, 7] } }
+        },
+        "7": {
+          "id": 7,
+          "crate_id": 0,
+          "name": null,
+          "visibility": "public",
+          "inner": {
+            "use": {
+              "source": "crate::Wrapper",
+              "name": "Alias",
+              "id": 2,
+              "is_glob": false
+            }
+          }
+        },
+        "1": {
+          "id": 1,
+          "crate_id": 0,
+          "name": "add",
+          "visibility": "public",
+          "docs": "Adds two\nnumbers together.",
+          "span": {
+            "filename": "src/lib.rs",
+            "begin": [3, 1],
+            "end": [5, 2]
+          },
+          "inner": {
+            "function": {
+              "sig": {
+                "inputs": [
+                  ["a", { "primitive": "i32" }],
+                  ["b", { "primitive": "i32" }]
+                ],
+                "output": { "primitive": "i32" }
+              },
+              "generics": { "params": [], "where_predicates": [] },
+              "header": {
+                "is_const": false,
+                "is_unsafe": false,
+                "is_async": false,
+                "abi": "Rust"
+              }
+            }
+          }
+        },
+        "2": {
+          "id": 2,
+          "crate_id": 0,
+          "name": "Wrapper",
+          "visibility": "public",
+          "docs": null,
+          "inner": {
+            "struct": {
+              "kind": { "tuple": [3] },
+              "generics": { "params": [], "where_predicates": [] },
+              "impls": [4, 6]
+            }
+          }
+        },
+        "3": {
+          "id": 3,
+          "crate_id": 0,
+          "name": "0",
+          "visibility": "public",
+          "inner": { "struct_field": { "primitive": "u8" } }
+        },
+        "4": {
+          "id": 4,
+          "crate_id": 0,
+          "name": null,
+          "visibility": "default",
+          "inner": {
+            "impl": {
+              "is_synthetic": false,
+              "generics": { "params": [], "where_predicates": [] },
+              "trait": null,
+              "for": { "resolved_path": { "path": "Wrapper", "id": 2, "args": null } },
+              "blanket_impl": null,
+              "items": [5]
+            }
+          }
+        },
+        "6": {
+          "id": 6,
+          "crate_id": 0,
+          "name": null,
+          "visibility": "default",
+          "inner": {
+            "impl": {
+              "is_synthetic": true,
+              "generics": { "params": [], "where_predicates": [] },
+              "trait": { "path": "Send", "id": 9, "args": null },
+              "for": { "resolved_path": { "path": "Wrapper", "id": 2, "args": null } },
+              "blanket_impl": null,
+              "items": []
+            }
+          }
+        },
+        "5": {
+          "id": 5,
+          "crate_id": 0,
+          "name": "get",
+          "visibility": "public",
+          "docs": "Returns the inner value, unlike [`add`].",
+          "links": { "`add`": 1 },
+          "inner": {
+            "function": {
+              "sig": {
+                "inputs": [
+                  ["self", {
+                    "borrowed_ref": {
+                      "lifetime": null,
+                      "is_mutable": false,
+                      "type": { "generic": "Self" }
+                    }
+                  }]
+                ],
+                "output": { "primitive": "u8" }
+              },
+              "generics": { "params": [], "where_predicates": [] },
+              "header": { "abi": "Rust" }
+            }
+          }
+        }
+      },
+      "paths": {
+        "0": { "crate_id": 0, "path": ["crate"], "kind": "module" },
+        "1": { "crate_id": 0, "path": ["crate", "add"], "kind": "function" },
+        "2": { "crate_id": 0, "path": ["crate", "Wrapper"], "kind": "struct" }
+      }
+    });
 
-    let variants = vec!["Success".to_string(), "Error(String)".to_string()];
+    fs::write(
+      temp_dir.path().join("target/doc/crate.json"),
+      json.to_string(),
+    )
+    .unwrap();
 
-    let enum_html = enum_html(
-      "Result",
-      "pub enum Result",
-      Some("A type representing either success or failure."),
-      &variants,
+    fs::write(
+      temp_dir.path().join("target/doc/crate/fn.ignored.html"),
+      function_html("ignored", "pub fn ignored()", None),
+    )
+    .unwrap();
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    assert_eq!(
+      result.items,
+      vec![
+        Item::Module {
+          name: "crate".to_string(),
+          path: "crate".to_string(),
+          description: Some("The crate root.".to_string()),
+          items: vec!["add".to_string(), "Wrapper".to_string()],
+          stability: Stability::default(),
+          examples: Vec::new(),
+          links: Vec::new(),
+          aliases: Vec::new(),
+          source: None,
+          reexports: vec![ReExport {
+            path: "crate::Alias".to_string(),
+            original: "crate::Wrapper".to_string(),
+            crate_name: "crate".to_string(),
+          }],
+        },
+        Item::Struct {
+          name: "Wrapper".to_string(),
+          path: "crate::Wrapper".to_string(),
+          signature: "pub struct Wrapper(pub u8);".to_string(),
+          description: None,
+          fields: vec![Field {
+            name: "0".to_string(),
+            ty: "u8".to_string(),
+            visibility: "pub".to_string(),
+            description: None,
+          }],
+          implementations: vec![
+            Implementation {
+              signature: "impl Wrapper".to_string(),
+              trait_name: None,
+              generics: Vec::new(),
+              where_clause: Vec::new(),
+              kind: ImplementationKind::Inherent,
+              methods: vec![Item::Function {
+                name: "get".to_string(),
+                path: "crate::Wrapper::get".to_string(),
+                signature: "pub fn get(&self) -> u8".to_string(),
+                parsed_signature: FunctionSignature::parse(
+                  "pub fn get(&self) -> u8"
+                ),
+                description: Some(
+                  "Returns the inner value, unlike [`add`](crate::add)."
+                    .to_string(),
+                ),
+                stability: Stability::default(),
+                examples: Vec::new(),
+                links: vec!["crate::add".to_string()],
+                aliases: Vec::new(),
+                source: None,
+              }],
+            },
+            Implementation {
+              signature: "impl Send for Wrapper".to_string(),
+              trait_name: Some("Send".to_string()),
+              generics: Vec::new(),
+              where_clause: Vec::new(),
+              kind: ImplementationKind::Auto,
+              methods: Vec::new(),
+            },
+          ],
+          stability: Stability::default(),
+          examples: Vec::new(),
+          links: Vec::new(),
+          aliases: vec!["crate::Alias".to_string()],
+          source: None,
+        },
+        Item::Function {
+          name: "add".to_string(),
+          path: "crate::add".to_string(),
+          signature: "pub fn add(a: i32, b: i32) -> i32".to_string(),
+          parsed_signature: FunctionSignature::parse(
+            "pub fn add(a: i32, b: i32) -> i32"
+          ),
+          description: Some("Adds two\nnumbers together.".to_string()),
+          stability: Stability::default(),
+          examples: Vec::new(),
+          links: Vec::new(),
+          aliases: Vec::new(),
+          source: Some("src/lib.rs#3-5".to_string()),
+        },
+      ]
     );
 
-    fs::write(crate_path.join("enum.Result.html"), enum_html).unwrap();
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      path: Some("crate::add".to_string()),
+      plain_text: Some(true),
+      ..Default::default()
+    };
+
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
+
+    let Item::Function { description, .. } = &result.items[0] else {
+      panic!("expected a function, got {:?}", result.items[0]);
+    };
+
+    assert_eq!(description.as_deref(), Some("Adds two numbers together."));
+
+    let overview = super::crate_overview(
+      &CrateOverviewRequest {
+        name: "crate".to_string(),
+        ..Default::default()
+      },
+      &doc_path,
+      &Cache::new(&doc_path, false),
+    )
+    .unwrap();
 
-    let request = LookupCrateRequest {
-      name: "crate".to_string(),
-      item_type: None,
-      query: None,
-      limit: None,
-      offset: None,
-    };
+    assert_eq!(overview.version.as_deref(), Some("0.3.1"));
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    assert_eq!(overview.description.as_deref(), Some("The crate root."));
 
     assert_eq!(
-      result.items,
-      vec![Item::Enum {
-        name: "Result".to_string(),
-        signature: "pub enum Result".to_string(),
-        description: Some(
-          "A type representing either success or failure.".to_string()
-        ),
-        variants: vec!["Success".to_string(), "Error(String)".to_string()],
-      }]
+      overview.item_counts,
+      Some(BTreeMap::from([
+        ("function".to_string(), 1),
+        ("struct".to_string(), 1)
+      ]))
     );
+
+    assert_eq!(super::list_crates(&doc_path).unwrap(), vec!["crate"]);
   }
 
   #[test]
-  fn parse_module() {
+  fn parse_rustdoc_json_cfg() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
-    let crate_path = temp_dir.path().join("target/doc/crate");
-
-    let items = vec!["function_a".to_string(), "struct_b".to_string()];
+    let function = |id: u32, name: &str, attribute: serde_json::Value| {
+      serde_json::json!({
+        "id": id,
+        "crate_id": 0,
+        "name": name,
+        "visibility": "public",
+        "attrs": [attribute],
+        "inner": {
+          "function": {
+            "sig": { "inputs": [], "output": null },
+            "generics": { "params": [], "where_predicates": [] },
+            "header": { "abi": "Rust" }
+          }
+        }
+      })
+    };
 
-    let module_html =
-      module_html("utils", Some("Utility functions and types."), &items);
+    let json = serde_json::json!({
+      "root": 0,
+      "format_version": 39,
+      "index": {
+        "0": {
+          "id": 0,
+          "crate_id": 0,
+          "name": "crate",
+          "visibility": "public",
+          "inner": { "module": { "is_crate": true, "items": [1, 2] } }
+        },
+        "1": function(1, "nested", "#[cfg(all(unix, feature = \"x\"))]".into()),
+        "2": function(
+          2,
+          "documented",
+          serde_json::json!({
+            "other": "#[doc(cfg(any(feature = \"a\", not(feature = \"b\"))))]"
+          })
+        )
+      },
+      "paths": {
+        "0": { "crate_id": 0, "path": ["crate"], "kind": "module" },
+        "1": { "crate_id": 0, "path": ["crate", "nested"], "kind": "function" },
+        "2": {
+          "crate_id": 0,
+          "path": ["crate", "documented"],
+          "kind": "function"
+        }
+      }
+    });
 
-    fs::write(crate_path.join("module.index.html"), module_html).unwrap();
+    fs::write(
+      temp_dir.path().join("target/doc/crate.json"),
+      json.to_string(),
+    )
+    .unwrap();
 
     let request = LookupCrateRequest {
       name: "crate".to_string(),
-      item_type: None,
-      query: None,
-      limit: None,
-      offset: None,
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
 
     assert_eq!(
-      result.items,
-      vec![Item::Module {
-        name: "index".to_string(),
-        description: Some("Utility functions and types.".to_string()),
-        items: vec!["function_a".to_string(), "struct_b".to_string()],
-      }]
+      result
+        .items
+        .iter()
+        .filter(|item| item.path() != "crate")
+        .map(|item| (
+          item.path(),
+          item.stability().cfg.as_deref(),
+          item.stability().required_features.clone()
+        ))
+        .collect::<Vec<_>>(),
+      vec![
+        (
+          "crate::documented",
+          Some(r#"any(feature = "a", not(feature = "b"))"#),
+          vec!["a".to_string()]
+        ),
+        (
+          "crate::nested",
+          Some(r#"all(unix, feature = "x")"#),
+          vec!["x".to_string()]
+        ),
+      ]
     );
   }
 
   #[test]
-  fn filter_by_item_type() {
+  fn crate_overview() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
     let crate_path = temp_dir.path().join("target/doc/crate");
 
-    let function_html =
-      function_html("add", "pub fn add(a: i32, b: i32) -> i32", None);
+    fs::write(
+      crate_path.join("index.html"),
+      r##"<!DOCTYPE html><html><body>
+      <nav class="sidebar"><div class="sidebar-crate"><h2><a href="../crate/index.html">crate</a><span class="version">1.2.0</span></h2></div></nav>
+      <pre class="rust item-decl"><code>mod crate</code></pre>
+      <details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>Tools for <strong>testing</strong>.</p></div></details>
+      <h2 id="modules" class="section-header">Modules</h2><dl class="item-table">
+      <dt><a class="mod" href="io/index.html" title="mod crate::io">io</a></dt><dd>Reading and writing.</dd>
+      <dt><a class="mod" href="sync/index.html" title="mod crate::sync">sync</a></dt><dd></dd>
+      </dl>
+      <h2 id="functions" class="section-header">Functions</h2><dl class="item-table">
+      <dt><a class="fn" href="fn.run.html" title="fn crate::run">run</a></dt><dd>Runs it.</dd>
+      </dl>
+      </body></html>"##,
+    )
+    .unwrap();
 
-    let struct_html = struct_html("MyStruct", "pub struct MyStruct", None, &[]);
+    for module in ["io", "sync"] {
+      fs::create_dir_all(crate_path.join(module)).unwrap();
 
-    fs::write(crate_path.join("fn.add.html"), function_html).unwrap();
-    fs::write(crate_path.join("struct.MyStruct.html"), struct_html).unwrap();
+      fs::write(
+        crate_path.join(module).join("index.html"),
+        module_html(module, None, &[]),
+      )
+      .unwrap();
+    }
 
-    let request = LookupCrateRequest {
+    fs::write(
+      crate_path.join("fn.run.html"),
+      function_html("run", "pub fn run()", Some("Runs it.")),
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("io/fn.read.html"),
+      function_html("read", "pub fn read()", None),
+    )
+    .unwrap();
+
+    let request = CrateOverviewRequest {
       name: "crate".to_string(),
-      item_type: Some("function".to_string()),
-      query: None,
-      limit: None,
-      offset: None,
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let overview =
+      super::crate_overview(&request, &doc_path, &Cache::new(&doc_path, false))
+        .unwrap();
+
+    assert_eq!(overview.version.as_deref(), Some("1.2.0"));
 
     assert_eq!(
-      result.items,
-      vec![Item::Function {
-        name: "add".to_string(),
-        signature: "pub fn add(a: i32, b: i32) -> i32".to_string(),
-        description: None,
-      }]
+      overview.description.as_deref(),
+      Some("Tools for **testing**.")
+    );
+
+    assert_eq!(
+      overview.modules,
+      Some(vec![
+        ModuleSummary {
+          name: "io".to_string(),
+          path: "crate::io".to_string(),
+          summary: Some("Reading and writing.".to_string()),
+        },
+        ModuleSummary {
+          name: "sync".to_string(),
+          path: "crate::sync".to_string(),
+          summary: None,
+        },
+      ])
+    );
+
+    assert_eq!(
+      overview.item_counts,
+      Some(BTreeMap::from([
+        ("function".to_string(), 2),
+        ("module".to_string(), 2)
+      ]))
+    );
+
+    assert!(overview.items.is_empty());
+
+    assert_eq!(
+      super::extract_version(&Html::parse_document(
+        r#"<nav class="sidebar"><div class="block version"><p>Version 0.9.0</p></div></nav>"#
+      )),
+      Some("0.9.0".to_string())
     );
   }
 
   #[test]
-  fn filter_by_query() {
+  fn search_index_listing() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
     let crate_path = temp_dir.path().join("target/doc/crate");
 
-    let function1_html = function_html(
-      "add",
-      "pub fn add(a: i32, b: i32) -> i32",
-      Some("Adds numbers"),
-    );
+    fs::write(
+      crate_path.join("fn.add.html"),
+      function_html("add", "pub fn add(a: i32, b: i32) -> i32", None),
+    )
+    .unwrap();
 
-    let function2_html = function_html(
-      "subtract",
-      "pub fn subtract(a: i32, b: i32) -> i32",
-      Some("Subtracts numbers"),
+    fs::write(crate_path.join("fn.broken.html"), [0xff, 0xfe]).unwrap();
+
+    fs::write(
+      crate_path.join("fn.redirect.html"),
+      function_html("redirect", "pub fn redirect()", None),
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("all.html"),
+      r#"<ul class="all-items"><li><a href="fn.add.html">add</a></li><li><a href="fn.broken.html">broken</a></li></ul>"#,
+    )
+    .unwrap();
+
+    fs::write(
+      temp_dir.path().join("target/doc/search-index.js"),
+      r#"var searchIndex = new Map(JSON.parse('[\
+["crate",{"t":"HHCF","n":["add","broken","nested","Inner"],"q":[[0,"crate"],[3,"crate::nested"]],"D":"CAAA"}]\
+]'));
+if (typeof exports !== 'undefined') exports.searchIndex = searchIndex;"#,
+    )
+    .unwrap();
+
+    let shards = temp_dir.path().join("target/doc/search.desc/crate");
+
+    fs::create_dir_all(&shards).unwrap();
+
+    fs::write(
+      shards.join("crate-desc-0-.js"),
+      r#"searchState.loadedDescShard("crate", 0, "[\"Sums two <code>i32</code> values\",\"\"]")"#,
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("index.html"),
+      module_html("crate", None, &[]),
+    )
+    .unwrap();
+
+    fs::create_dir_all(crate_path.join("nested")).unwrap();
+
+    fs::write(
+      crate_path.join("nested/index.html"),
+      module_html("nested", None, &[]),
+    )
+    .unwrap();
+
+    fs::write(
+      crate_path.join("nested/struct.Inner.html"),
+      struct_html("Inner", "pub struct Inner;", None, &[]),
+    )
+    .unwrap();
+
+    let listing = || {
+      let search_index =
+        load_search_index(&temp_dir.path().join("target/doc"), "crate")
+          .unwrap();
+
+      list_entries(&crate_path, "crate", search_index.as_ref())
+        .unwrap()
+        .iter()
+        .map(|entry| (entry.path(), entry.description.clone()))
+        .collect::<Vec<_>>()
+    };
+
+    let summary = Some("Sums two i32 values".to_string());
+
+    // `all.html` doesn't list `Inner`, so only the index finds it.
+    assert_eq!(
+      listing(),
+      vec![
+        ("crate".to_string(), None),
+        ("crate::add".to_string(), summary.clone()),
+        ("crate::broken".to_string(), None),
+        ("crate::nested".to_string(), None),
+        ("crate::nested::Inner".to_string(), None),
+      ]
     );
 
-    fs::write(crate_path.join("fn.add.html"), function1_html).unwrap();
-    fs::write(crate_path.join("fn.subtract.html"), function2_html).unwrap();
+    // An index naming pages that don't exist falls back to walking the tree.
+    fs::remove_file(crate_path.join("nested/struct.Inner.html")).unwrap();
+
+    assert_eq!(
+      listing(),
+      vec![
+        ("crate".to_string(), None),
+        ("crate::add".to_string(), summary),
+        ("crate::broken".to_string(), None),
+        ("crate::nested".to_string(), None),
+      ]
+    );
 
     let request = LookupCrateRequest {
       name: "crate".to_string(),
-      item_type: None,
-      query: Some("add".to_string()),
-      limit: None,
-      offset: None,
+      query: Some("sums two i32".to_string()),
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
 
     assert_eq!(
       result.items,
       vec![Item::Function {
         name: "add".to_string(),
+        path: "crate::add".to_string(),
         signature: "pub fn add(a: i32, b: i32) -> i32".to_string(),
-        description: Some("Adds numbers".to_string()),
+        parsed_signature: FunctionSignature::parse(
+          "pub fn add(a: i32, b: i32) -> i32"
+        ),
+        description: None,
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
       }]
     );
+
+    let request = LookupCrateRequest {
+      name: "crate".to_string(),
+      limit: Some(1),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false))
+        .unwrap()
+        .items
+        .len(),
+      1
+    );
   }
 
   #[test]
-  fn pagination() {
+  fn index_cache() {
     let temp_dir = TempDir::new().unwrap();
 
     let doc_path = initialize(&temp_dir);
 
     let crate_path = temp_dir.path().join("target/doc/crate");
 
-    for i in 0..5 {
-      let function_html = function_html(
-        &format!("func_{}", i),
-        &format!("pub fn func_{}()", i),
-        None,
-      );
-
-      fs::write(
-        crate_path.join(format!("fn.func_{}.html", i)),
-        function_html,
-      )
-      .unwrap();
-    }
+    fs::write(
+      crate_path.join("fn.add.html"),
+      function_html("add", "pub fn add()", Some("Adds numbers")),
+    )
+    .unwrap();
 
     let request = LookupCrateRequest {
       name: "crate".to_string(),
-      item_type: None,
-      query: None,
-      limit: Some(2),
-      offset: Some(1),
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let cache = Cache::new(&doc_path, true);
 
-    assert_eq!(result.items.len(), 2);
-  }
+    let description = |cache: &Cache| {
+      lookup_crate(&request, &doc_path, cache).unwrap().items[0]
+        .search_items()
+        .1
+        .clone()
+    };
 
-  #[test]
-  fn extract_item_name() {
-    assert_eq!(super::extract_item_name("fn.add.html").unwrap(), "add");
+    assert_eq!(description(&cache), Some("Adds numbers".to_string()));
 
-    assert_eq!(
-      super::extract_item_name("struct.MyStruct.html").unwrap(),
-      "MyStruct"
-    );
+    let cache_file = temp_dir.path().join("target/doc/.crates-mcp/crate.json");
 
-    assert_eq!(
-      super::extract_item_name("enum.Result.html").unwrap(),
-      "Result"
+    assert!(
+      !fs::read_to_string(&cache_file)
+        .unwrap()
+        .contains("Adds numbers")
     );
 
-    assert_eq!(
-      super::extract_item_name("module.index.html").unwrap(),
-      "index"
-    );
+    let shards = temp_dir.path().join("target/doc/.crates-mcp/items/crate");
 
-    assert!(super::extract_item_name("invalid").is_err());
+    let shard = fs::read_dir(&shards)
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .collect::<Vec<_>>();
 
-    assert!(super::extract_item_name("fn.add.txt").is_err());
-  }
+    assert_eq!(shard.len(), 1);
 
-  #[test]
-  fn extract_method_name() {
-    assert_eq!(super::extract_method_name("fn new() -> Self"), "new");
+    let cached = fs::read_to_string(&shard[0]).unwrap();
 
-    assert_eq!(
-      super::extract_method_name("fn get_value(&self) -> i32"),
-      "get_value"
-    );
+    fs::write(&shard[0], cached.replace("Adds numbers", "Cached")).unwrap();
+
+    assert_eq!(description(&cache), Some("Cached".to_string()));
 
     assert_eq!(
-      super::extract_method_name(
-        "fn complex_method<T>(self, param: T) where T: Clone"
-      ),
-      "complex_method<T>"
+      description(&Cache::new(&doc_path, false)),
+      Some("Adds numbers".to_string())
     );
 
-    assert_eq!(super::extract_method_name("invalid signature"), "unknown");
+    fs::write(
+      crate_path.join("fn.add.html"),
+      function_html("add", "pub fn add()", Some("Adds two numbers")),
+    )
+    .unwrap();
+
+    assert_eq!(description(&cache), Some("Adds two numbers".to_string()));
+
+    assert_eq!(super::list_crates(&doc_path).unwrap(), vec!["crate"]);
+
+    assert_eq!(cache.clear().unwrap(), 1);
+
+    assert!(!cache_file.exists());
+
+    assert!(!shards.exists());
   }
 
   #[test]
-  fn html_to_text() {
-    assert_eq!(
-      super::html_to_text("<p>Hello <strong>world</strong></p>".to_string()),
-      "Hello world"
-    );
+  fn concurrent_index_cache() {
+    let temp_dir = TempDir::new().unwrap();
 
-    assert_eq!(
-      super::html_to_text("&lt;div&gt; &amp; &quot;test&quot;".to_string()),
-      "<div> & \"test\""
-    );
+    let doc_path = initialize(&temp_dir);
 
-    assert_eq!(
-      super::html_to_text("   Multiple    spaces   ".to_string()),
-      "Multiple spaces"
-    );
+    for module in ["a", "b", "c"] {
+      let module_path = temp_dir.path().join("target/doc/crate").join(module);
 
-    assert_eq!(
-      super::html_to_text("<code>fn test() -&gt; bool</code>".to_string()),
-      "fn test() -> bool"
+      fs::create_dir_all(&module_path).unwrap();
+
+      fs::write(
+        module_path.join("fn.f.html"),
+        function_html("f", "pub fn f()", Some(module)),
+      )
+      .unwrap();
+    }
+
+    let cache = Cache::new(&doc_path, true);
+
+    let paths = || {
+      let request = LookupCrateRequest {
+        name: "crate".to_string(),
+        ..Default::default()
+      };
+
+      lookup_crate(&request, &doc_path, &cache)
+        .unwrap()
+        .items
+        .iter()
+        .map(|item| item.path().to_string())
+        .collect::<Vec<_>>()
+    };
+
+    let results = std::thread::scope(|scope| {
+      (0..8)
+        .map(|_| scope.spawn(paths))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect::<Vec<_>>()
+    });
+
+    let expected = vec!["crate::a::f", "crate::b::f", "crate::c::f"];
+
+    assert!(results.iter().all(|paths| *paths == expected));
+
+    // Every shard survived the concurrent stores, and no temporary files were
+    // left behind.
+    let shards = temp_dir.path().join("target/doc/.crates-mcp/items/crate");
+
+    assert_eq!(fs::read_dir(&shards).unwrap().count(), 3);
+
+    assert!(
+      fs::read_dir(temp_dir.path().join("target/doc/.crates-mcp"))
+        .unwrap()
+        .chain(fs::read_dir(&shards).unwrap())
+        .all(|entry| {
+          !entry
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .starts_with(".tmp")
+        })
     );
   }
 
@@ -835,21 +5913,75 @@ mod tests {
 
     let request = LookupCrateRequest {
       name: "crate".to_string(),
-      item_type: None,
-      query: None,
-      limit: None,
-      offset: None,
+      ..Default::default()
     };
 
-    let result = lookup_crate(&request, &doc_path).unwrap();
+    let result =
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false)).unwrap();
 
     assert_eq!(
       result.items,
       vec![Item::Function {
         name: "nested_func".to_string(),
+        path: "crate::submodule::nested_func".to_string(),
         signature: "pub fn nested_func()".to_string(),
+        parsed_signature: FunctionSignature::parse("pub fn nested_func()"),
         description: None,
+        stability: Stability::default(),
+        examples: Vec::new(),
+        links: Vec::new(),
+        aliases: Vec::new(),
+        source: None,
       }]
     );
   }
+
+  #[test]
+  fn filter_by_path() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let doc_path = initialize(&temp_dir);
+
+    let crate_path = temp_dir.path().join("target/doc/crate");
+
+    for module in ["io", "io_util", "io/error"] {
+      fs::create_dir_all(crate_path.join(module)).unwrap();
+
+      fs::write(
+        crate_path.join(module).join("struct.Error.html"),
+        struct_html("Error", "pub struct Error", None, &[]),
+      )
+      .unwrap();
+    }
+
+    fs::write(
+      crate_path.join("struct.Error.html"),
+      struct_html("Error", "pub struct Error", None, &[]),
+    )
+    .unwrap();
+
+    let paths = |prefix: &str| {
+      let request = LookupCrateRequest {
+        name: "crate".to_string(),
+        path: Some(prefix.to_string()),
+        ..Default::default()
+      };
+
+      lookup_crate(&request, &doc_path, &Cache::new(&doc_path, false))
+        .unwrap()
+        .items
+        .iter()
+        .map(|item| item.path().to_string())
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      paths("crate::io"),
+      vec!["crate::io::Error", "crate::io::error::Error"]
+    );
+
+    assert_eq!(paths("crate::io::error::"), vec!["crate::io::error::Error"]);
+
+    assert_eq!(paths("crate").len(), 4);
+  }
 }
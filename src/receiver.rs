@@ -0,0 +1,11 @@
+use super::*;
+
+/// How a method takes `self`: by value, by shared or mutable reference, or
+/// through another type such as `Box<Self>` or `Pin<&mut Self>`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Receiver {
+  Value,
+  Ref,
+  RefMut,
+  Typed(String),
+}
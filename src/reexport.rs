@@ -0,0 +1,21 @@
+use super::*;
+
+/// A `pub use` in a module: the path it makes public, the path of the item
+/// (or module, for globs) it points at, and the crate that defines it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ReExport {
+  pub path: String,
+  pub original: String,
+  #[serde(rename = "crate")]
+  pub crate_name: String,
+}
+
+impl ReExport {
+  pub fn new(path: String, original: String) -> Self {
+    Self {
+      crate_name: original.split("::").next().unwrap_or_default().to_string(),
+      path,
+      original,
+    }
+  }
+}
@@ -1,5 +1,49 @@
 use super::*;
 
+/// Crates are exposed as resources at `crate://<name>`.
+const RESOURCE_SCHEME: &str = "crate://";
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ClearCacheRequest {}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct CrateOverviewRequest {
+  #[schemars(description = "The name of the Rust crate")]
+  pub name: String,
+  #[schemars(
+    description = "Return the crate's docs as flattened plain text instead of Markdown (default: false)"
+  )]
+  pub plain_text: Option<bool>,
+  #[schemars(
+    description = "Target triple whose docs to read, from 'cargo doc --target' (e.g., 'wasm32-unknown-unknown', default: host)"
+  )]
+  pub target: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct DiffDocsRequest {
+  #[schemars(
+    description = "Snapshot name or documentation directory to compare from (e.g., 'before-upgrade', '../old/target/doc')"
+  )]
+  pub old: String,
+  #[schemars(
+    description = "Snapshot name or documentation directory to compare to (default: the current documentation)"
+  )]
+  pub new: Option<String>,
+  #[schemars(
+    description = "Only compare this crate (default: every crate found on both sides)"
+  )]
+  pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FindImplementorsRequest {
+  #[schemars(
+    description = "Path of the trait (e.g., 'serde::Serialize', 'tower::Service')"
+  )]
+  pub trait_path: String,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GenerateDocsRequest {
   #[schemars(
@@ -8,10 +52,34 @@ pub struct GenerateDocsRequest {
   pub flags: Option<Vec<String>>,
 }
 
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct GetSourceRequest {
+  #[schemars(
+    description = "Path of the item (e.g., 'tokio::sync::Mutex', 'tokio::sync::Mutex::lock')"
+  )]
+  pub path: String,
+  #[schemars(
+    description = "Return the whole file the item is defined in (default: false)"
+  )]
+  pub whole_file: Option<bool>,
+  #[schemars(
+    description = "First line of the file to return instead of the item's definition"
+  )]
+  pub start_line: Option<usize>,
+  #[schemars(
+    description = "Last line of the file to return instead of the item's definition"
+  )]
+  pub end_line: Option<usize>,
+  #[schemars(
+    description = "Target triple whose docs to read, from 'cargo doc --target' (e.g., 'wasm32-unknown-unknown', default: host)"
+  )]
+  pub target: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListCratesRequest {}
 
-#[derive(Debug, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 pub struct LookupCrateRequest {
   #[schemars(description = "The name of the Rust crate")]
   pub name: String,
@@ -24,23 +92,87 @@ pub struct LookupCrateRequest {
   )]
   pub offset: Option<usize>,
   #[schemars(
-    description = "Filter by item type: function, struct, enum, trait, macro, type, constant, module"
+    description = "Filter by item type: function, struct, enum, trait, macro, type, constant, module, union, static, trait_alias, primitive, keyword, attribute_macro, derive_macro, foreign_type"
   )]
   pub item_type: Option<String>,
   #[schemars(
-    description = "Search term to filter items by name or description"
+    description = "Search term to filter items by name or one-line summary"
   )]
   pub query: Option<String>,
+  #[schemars(
+    description = "Only return items under this module path (e.g., 'tokio::sync')"
+  )]
+  pub path: Option<String>,
+  #[schemars(
+    description = "Omit auto-trait (Send, Sync, Unpin, ...) and blanket implementations from types (default: false)"
+  )]
+  pub exclude_auto_impls: Option<bool>,
+  #[schemars(description = "Omit deprecated items (default: false)")]
+  pub exclude_deprecated: Option<bool>,
+  #[schemars(
+    description = "Only return items gated behind this crate feature (e.g., 'rt')"
+  )]
+  pub feature: Option<String>,
+  #[schemars(
+    description = "Return descriptions as flattened plain text instead of Markdown (default: false)"
+  )]
+  pub plain_text: Option<bool>,
+  #[schemars(
+    description = "Target triple whose docs to read, from 'cargo doc --target' (e.g., 'wasm32-unknown-unknown', default: host)"
+  )]
+  pub target: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct SaveSnapshotRequest {
+  #[schemars(
+    description = "Name to save the snapshot under (e.g., 'before-upgrade')"
+  )]
+  pub name: String,
+  #[schemars(
+    description = "Crates to save (default: every crate in the documentation directory)"
+  )]
+  pub crates: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct SearchByTypeRequest {
+  #[schemars(
+    description = "Parameter types and return type separated by '->' (e.g., '&str -> Url', '-> impl Stream<Item = Bytes>', 'Url, Duration -> bool'), or types that may appear anywhere in the signature"
+  )]
+  pub query: String,
+  #[schemars(description = "Only search this crate (default: all crates)")]
+  pub name: Option<String>,
+  #[schemars(
+    description = "Maximum number of functions to return (default: 50)"
+  )]
+  pub limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct SearchExamplesRequest {
+  #[schemars(
+    description = "Identifier the example code must mention (e.g., 'HashMap::entry')"
+  )]
+  pub query: String,
+  #[schemars(description = "Only search this crate (default: all crates)")]
+  pub name: Option<String>,
+  #[schemars(
+    description = "Maximum number of examples to return (default: no limit)"
+  )]
+  pub limit: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Router {
+  log_level: Arc<Mutex<LoggingLevel>>,
+  options: Options,
   tool_router: ToolRouter<Self>,
 }
 
 impl Default for Router {
   fn default() -> Self {
-    Self::new()
+    Self::new(Options::default())
   }
 }
 
@@ -48,21 +180,199 @@ impl Default for Router {
 impl ServerHandler for Router {
   fn get_info(&self) -> ServerInfo {
     ServerInfo {
-      capabilities: ServerCapabilities::builder().enable_tools().build(),
+      capabilities: ServerCapabilities::builder()
+        .enable_logging()
+        .enable_resources()
+        .enable_resources_list_changed()
+        .enable_tools()
+        .build(),
       instructions: Some("Find information about relevant Rust crates".into()),
       ..Default::default()
     }
   }
+
+  async fn list_resources(
+    &self,
+    _request: Option<PaginatedRequestParam>,
+    _context: RequestContext<RoleServer>,
+  ) -> Result<ListResourcesResult, McpError> {
+    Ok(ListResourcesResult::with_all_items(
+      list_crate_targets(self.options.doc_path(), self.options.std_doc_path())?
+        .into_keys()
+        .map(|name| {
+          let mut resource =
+            RawResource::new(format!("{RESOURCE_SCHEME}{name}"), &name);
+
+          resource.description = Some(format!("Documentation for {name}"));
+          resource.mime_type = Some("application/json".into());
+
+          resource.no_annotation()
+        })
+        .collect(),
+    ))
+  }
+
+  async fn read_resource(
+    &self,
+    request: ReadResourceRequestParam,
+    _context: RequestContext<RoleServer>,
+  ) -> Result<ReadResourceResult, McpError> {
+    let Some(name) = request.uri.strip_prefix(RESOURCE_SCHEME) else {
+      return Err(McpError::resource_not_found(
+        format!("unknown resource '{}'", request.uri),
+        None,
+      ));
+    };
+
+    let content = self
+      .lookup_crate_impl(LookupCrateRequest {
+        name: name.to_string(),
+        ..Default::default()
+      })
+      .await?;
+
+    Ok(ReadResourceResult {
+      contents: vec![ResourceContents::text(content, request.uri)],
+    })
+  }
+
+  async fn set_level(
+    &self,
+    request: SetLevelRequestParam,
+    _context: RequestContext<RoleServer>,
+  ) -> Result<(), McpError> {
+    *self.log_level.lock().unwrap() = request.level;
+    Ok(())
+  }
 }
 
 #[tool_router]
 impl Router {
-  pub fn new() -> Self {
+  pub fn new(options: Options) -> Self {
     Self {
+      log_level: Arc::new(Mutex::new(LoggingLevel::Info)),
+      options,
       tool_router: Self::tool_router(),
     }
   }
 
+  /// The minimum level of log messages the client asked to receive, shared
+  /// with the documentation watcher.
+  pub fn log_level(&self) -> Arc<Mutex<LoggingLevel>> {
+    self.log_level.clone()
+  }
+
+  #[tool(description = "Delete the on-disk index cache for all crates")]
+  fn clear_cache(
+    &self,
+    Parameters(ClearCacheRequest {}): Parameters<ClearCacheRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match Cache::new(self.options.doc_path(), true).clear() {
+      Ok(count) => Ok(CallToolResult::success(vec![Content::text(format!(
+        "Cleared cached index for {count} crate(s)"
+      ))])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  #[tool(
+    description = "Summarize a Rust crate: its root docs, version, top-level modules and item counts per kind"
+  )]
+  async fn crate_overview(
+    &self,
+    Parameters(parameters): Parameters<CrateOverviewRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.crate_overview_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn crate_overview_impl(
+    &self,
+    parameters: CrateOverviewRequest,
+  ) -> Result<String> {
+    let (path, cache) = (
+      self.options.doc_path_for(&parameters.name).to_string(),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&crate_overview(
+        &parameters,
+        &path,
+        &cache,
+      )?)?)
+    })
+    .await?
+  }
+
+  #[tool(
+    description = "Compare a crate's API between two documentation snapshots, reporting added, removed and changed items, fields, variants, methods and impls with their likely semver impact"
+  )]
+  async fn diff_docs(
+    &self,
+    Parameters(parameters): Parameters<DiffDocsRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.diff_docs_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn diff_docs_impl(
+    &self,
+    parameters: DiffDocsRequest,
+  ) -> Result<String> {
+    let (path, snapshots, cache) = (
+      self.options.doc_path().to_string(),
+      self.options.snapshot_dir(),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&diff_docs(
+        &parameters,
+        &path,
+        &snapshots,
+        &cache,
+      )?)?)
+    })
+    .await?
+  }
+
+  #[tool(
+    description = "Find the types across all documented crates that implement a trait"
+  )]
+  async fn find_implementors(
+    &self,
+    Parameters(parameters): Parameters<FindImplementorsRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.find_implementors_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn find_implementors_impl(
+    &self,
+    parameters: FindImplementorsRequest,
+  ) -> Result<String> {
+    let (path, std_path) = (
+      self.options.doc_path().to_string(),
+      self.options.std_doc_path().map(str::to_string),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&find_implementors(
+        &path,
+        std_path.as_deref(),
+        &parameters.trait_path,
+      )?)?)
+    })
+    .await?
+  }
+
   #[tool(description = "Generate documentation using 'cargo doc'")]
   fn generate_docs(
     &self,
@@ -78,8 +388,15 @@ impl Router {
     &self,
     parameters: &GenerateDocsRequest,
   ) -> Result<String, Error> {
-    let output = Command::new("cargo")
-      .arg("doc")
+    let mut command = Command::new("cargo");
+
+    command.arg("doc");
+
+    if let Some(manifest_path) = self.options.manifest_path() {
+      command.arg("--manifest-path").arg(manifest_path);
+    }
+
+    let output = command
       .args(parameters.flags.as_deref().unwrap_or(&[]))
       .output()
       .map_err(|error| anyhow!("failed to run cargo doc: {}", error))?;
@@ -95,34 +412,192 @@ impl Router {
     Ok(format!("{}{}", stdout, stderr))
   }
 
-  #[tool(description = "List all available Rust crates")]
+  #[tool(
+    description = "Get the source code of an item's definition, or of the file it is defined in"
+  )]
+  async fn get_source(
+    &self,
+    Parameters(parameters): Parameters<GetSourceRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.get_source_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn get_source_impl(
+    &self,
+    parameters: GetSourceRequest,
+  ) -> Result<String> {
+    let crate_name = parameters.path.split("::").next().unwrap_or_default();
+
+    let (path, workspace_root, cache) = (
+      self.options.doc_path_for(crate_name).to_string(),
+      self.options.workspace_root().map(Path::to_path_buf),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&get_source(
+        &parameters,
+        &path,
+        workspace_root.as_deref(),
+        &cache,
+      )?)?)
+    })
+    .await?
+  }
+
+  #[tool(
+    description = "List all available Rust crates, with the targets each is documented for when built with --target"
+  )]
   fn list_crates(
     &self,
     Parameters(ListCratesRequest {}): Parameters<ListCratesRequest>,
   ) -> Result<CallToolResult, McpError> {
-    match list_crates(DOC_PATH) {
+    match list_crate_targets(
+      self.options.doc_path(),
+      self.options.std_doc_path(),
+    ) {
       Ok(crates) => Ok(CallToolResult::success(vec![Content::text(
-        crates.join("\n"),
+        format_crate_targets(&crates),
       )])),
       Err(error) => Err(error.into()),
     }
   }
 
   #[tool(description = "Lookup information about a specific Rust crate")]
-  fn lookup_crate(
+  async fn lookup_crate(
     &self,
     Parameters(parameters): Parameters<LookupCrateRequest>,
   ) -> Result<CallToolResult, McpError> {
-    match self.lookup_crate_impl(&parameters) {
+    match self.lookup_crate_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn lookup_crate_impl(
+    &self,
+    parameters: LookupCrateRequest,
+  ) -> Result<String> {
+    let (path, cache) = (
+      self.options.doc_path_for(&parameters.name).to_string(),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&lookup_crate(
+        &parameters,
+        &path,
+        &cache,
+      )?)?)
+    })
+    .await?
+  }
+
+  #[tool(
+    description = "Save the current documentation of crates as a named snapshot, to compare against with diff_docs after regenerating it"
+  )]
+  async fn save_snapshot(
+    &self,
+    Parameters(parameters): Parameters<SaveSnapshotRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.save_snapshot_impl(parameters).await {
       Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
       Err(error) => Err(error.into()),
     }
   }
 
-  fn lookup_crate_impl(
+  async fn save_snapshot_impl(
     &self,
-    parameters: &LookupCrateRequest,
+    parameters: SaveSnapshotRequest,
   ) -> Result<String> {
-    Ok(serde_json::to_string(&lookup_crate(parameters, DOC_PATH)?)?)
+    let (path, snapshots, cache) = (
+      self.options.doc_path().to_string(),
+      self.options.snapshot_dir(),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      let crates = save_snapshot(&parameters, &path, &snapshots, &cache)?;
+
+      Ok(format!(
+        "Saved {} crate(s) to snapshot '{}' in {}: {}",
+        crates.len(),
+        parameters.name,
+        snapshots.display(),
+        crates.join(", ")
+      ))
+    })
+    .await?
+  }
+
+  #[tool(
+    description = "Find functions and methods across crates by their parameter and return types, ignoring references and matching generics by their bounds, with exact matches first"
+  )]
+  async fn search_by_type(
+    &self,
+    Parameters(parameters): Parameters<SearchByTypeRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.search_by_type_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn search_by_type_impl(
+    &self,
+    parameters: SearchByTypeRequest,
+  ) -> Result<String> {
+    let (path, std_path, cache) = (
+      self.options.doc_path().to_string(),
+      self.options.std_doc_path().map(str::to_string),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&search_by_type(
+        &parameters,
+        &path,
+        std_path.as_deref(),
+        &cache,
+      )?)?)
+    })
+    .await?
+  }
+
+  #[tool(
+    description = "Search documentation examples across crates for code that uses an identifier"
+  )]
+  async fn search_examples(
+    &self,
+    Parameters(parameters): Parameters<SearchExamplesRequest>,
+  ) -> Result<CallToolResult, McpError> {
+    match self.search_examples_impl(parameters).await {
+      Ok(content) => Ok(CallToolResult::success(vec![Content::text(content)])),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  async fn search_examples_impl(
+    &self,
+    parameters: SearchExamplesRequest,
+  ) -> Result<String> {
+    let (path, std_path, cache) = (
+      self.options.doc_path().to_string(),
+      self.options.std_doc_path().map(str::to_string),
+      self.options.cache(),
+    );
+
+    task::spawn_blocking(move || {
+      Ok(serde_json::to_string(&search_examples(
+        &parameters,
+        &path,
+        std_path.as_deref(),
+        &cache,
+      )?)?)
+    })
+    .await?
   }
 }
@@ -0,0 +1,1382 @@
+use {super::*, serde_json::Value};
+
+/// The version rustdoc JSON records for the crate, if one was passed to
+/// rustdoc with `--crate-version`.
+pub fn crate_version(file_path: &Path) -> Result<Option<String>> {
+  #[derive(Deserialize)]
+  struct Header {
+    crate_version: Option<String>,
+  }
+
+  Ok(
+    serde_json::from_str::<Header>(&fs::read_to_string(file_path)?)?
+      .crate_version,
+  )
+}
+
+pub fn parse_json_file(file_path: &Path) -> Result<Vec<Item>> {
+  let krate = serde_json::from_str::<Value>(&fs::read_to_string(file_path)?)?;
+
+  let index = krate
+    .get("index")
+    .and_then(Value::as_object)
+    .ok_or_else(|| anyhow!("rustdoc json is missing an item index"))?;
+
+  let paths = krate
+    .get("paths")
+    .and_then(Value::as_object)
+    .ok_or_else(|| anyhow!("rustdoc json is missing a path table"))?;
+
+  let mut document = Document {
+    index,
+    paths,
+    aliases: HashMap::new(),
+  };
+
+  document.aliases = document.aliases();
+
+  let mut items = paths
+    .iter()
+    .filter(|(_, summary)| summary["crate_id"].as_u64() == Some(0))
+    .filter_map(|(id, summary)| {
+      let path = summary["path"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>()
+        .join("::");
+
+      Some((index.get(id)?, path))
+    })
+    .filter(|(item, _)| item["visibility"] == "public")
+    .filter_map(|(item, path)| document.item(item, path))
+    .collect::<Vec<Item>>();
+
+  items.sort_by(|a, b| a.path().cmp(b.path()));
+
+  Ok(items)
+}
+
+fn id_key(id: &Value) -> Option<String> {
+  match id {
+    Value::Number(number) => Some(number.to_string()),
+    Value::String(string) => Some(string.clone()),
+    _ => None,
+  }
+}
+
+fn field<'a>(value: &'a Value, names: &[&str]) -> &'a Value {
+  names
+    .iter()
+    .map(|name| &value[*name])
+    .find(|value| !value.is_null())
+    .unwrap_or(&Value::Null)
+}
+
+struct Document<'a> {
+  index: &'a serde_json::Map<String, Value>,
+  paths: &'a serde_json::Map<String, Value>,
+  aliases: HashMap<String, Vec<String>>,
+}
+
+impl Document<'_> {
+  fn get(&self, id: &Value) -> Option<&Value> {
+    id_key(id).and_then(|key| self.index.get(&key))
+  }
+
+  /// The public paths each item is re-exported under, keyed by its own path.
+  fn aliases(&self) -> HashMap<String, Vec<String>> {
+    let mut aliases = HashMap::<String, Vec<String>>::new();
+
+    for (id, summary) in self.paths {
+      if summary["crate_id"].as_u64() != Some(0) || summary["kind"] != "module"
+      {
+        continue;
+      }
+
+      let (Some(module), Some(item)) =
+        (self.path(&Value::String(id.clone())), self.index.get(id))
+      else {
+        continue;
+      };
+
+      for reexport in self.reexports(&item["inner"]["module"], &module) {
+        if !reexport.original.ends_with("::*")
+          && reexport.original != reexport.path
+        {
+          aliases
+            .entry(reexport.original)
+            .or_default()
+            .push(reexport.path);
+        }
+      }
+    }
+
+    aliases
+  }
+
+  /// The public `use` items in a module.
+  fn reexports(&self, inner: &Value, module: &str) -> Vec<ReExport> {
+    self
+      .children(&inner["items"])
+      .into_iter()
+      .filter(|child| child["visibility"] == "public")
+      .filter_map(|child| {
+        let reexport = field(&child["inner"], &["use", "import"]);
+
+        let glob =
+          field(reexport, &["is_glob", "glob"]).as_bool() == Some(true);
+
+        let original = self
+          .path(&reexport["id"])
+          .or_else(|| reexport["source"].as_str().map(str::to_string))?;
+
+        Some(if glob {
+          ReExport::new(format!("{module}::*"), format!("{original}::*"))
+        } else {
+          ReExport::new(
+            format!("{module}::{}", reexport["name"].as_str()?),
+            original,
+          )
+        })
+      })
+      .collect()
+  }
+
+  fn path(&self, id: &Value) -> Option<String> {
+    let path = self.paths.get(&id_key(id)?)?["path"]
+      .as_array()?
+      .iter()
+      .filter_map(Value::as_str)
+      .collect::<Vec<_>>()
+      .join("::");
+
+    (!path.is_empty()).then_some(path)
+  }
+
+  /// The raw Markdown docs, with intra-doc links pointed at the paths rustdoc
+  /// resolved them to.
+  fn description(&self, item: &Value) -> Option<String> {
+    description(item).map(|docs| {
+      resolve_links(&docs, |destination| {
+        let links = &item["links"];
+
+        [destination, destination.trim_matches('`')]
+          .into_iter()
+          .find_map(|key| links.get(key))
+          .and_then(|id| self.path(id))
+      })
+      .0
+    })
+  }
+
+  /// Paths of the items referenced from an item's docs and signature.
+  fn links(&self, item: &Value) -> Vec<String> {
+    fn referenced_ids<'a>(value: &'a Value, ids: &mut Vec<&'a Value>) {
+      match value {
+        Value::Object(object) => {
+          for (key, value) in object {
+            if matches!(key.as_str(), "resolved_path" | "trait")
+              && let Some(id) = value.get("id")
+            {
+              ids.push(id);
+            }
+
+            referenced_ids(value, ids);
+          }
+        }
+        Value::Array(values) => {
+          values.iter().for_each(|value| referenced_ids(value, ids));
+        }
+        _ => {}
+      }
+    }
+
+    let mut ids = item["links"]
+      .as_object()
+      .map(|links| links.values().collect::<Vec<_>>())
+      .unwrap_or_default();
+
+    referenced_ids(&item["inner"], &mut ids);
+
+    let mut links = ids
+      .into_iter()
+      .filter_map(|id| self.path(id))
+      .collect::<Vec<_>>();
+
+    links.sort();
+    links.dedup();
+
+    links
+  }
+
+  fn children(&self, ids: &Value) -> Vec<&Value> {
+    ids
+      .as_array()
+      .map(|ids| ids.iter().filter_map(|id| self.get(id)).collect())
+      .unwrap_or_default()
+  }
+
+  fn item(&self, item: &Value, path: String) -> Option<Item> {
+    let name = item["name"].as_str()?.to_string();
+
+    let description = self.description(item);
+
+    let links = self.links(item);
+
+    let aliases = self.aliases.get(&path).cloned().unwrap_or_default();
+
+    let source = source(item);
+
+    let stability = stability(item);
+
+    let examples = examples(item);
+
+    let (kind, inner) = item["inner"].as_object()?.iter().next()?;
+
+    Some(match kind.as_str() {
+      "function" => {
+        let signature = self.function_signature(item, inner);
+
+        Item::Function {
+          parsed_signature: FunctionSignature::parse(&signature),
+          signature,
+          name,
+          path,
+          description,
+          stability,
+          examples,
+          links,
+          aliases,
+          source,
+        }
+      }
+      "struct" => Item::Struct {
+        signature: self.struct_signature(&name, inner),
+        fields: self.struct_fields(&inner["kind"]),
+        implementations: self.implementations(&inner["impls"], &path),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "enum" => Item::Enum {
+        signature: self.enum_signature(&name, inner),
+        implementations: self.implementations(&inner["impls"], &path),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+        variants: self.variants(inner),
+      },
+      "trait" => Item::Trait {
+        signature: trait_signature(&name, inner),
+        implementors: self.implementors(inner, &path),
+        supertraits: bound_list(&inner["bounds"]),
+        required_methods: self.trait_methods(inner, &path, false),
+        provided_methods: self.trait_methods(inner, &path, true),
+        associated_types: self.associated_types(inner),
+        associated_constants: self.associated_constants(inner),
+        dyn_compatible: field(inner, &["is_dyn_compatible", "is_object_safe"])
+          .as_bool()
+          .unwrap_or(true),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "macro" => Item::Macro {
+        signature: inner.as_str()?.to_string(),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "type_alias" | "typedef" => Item::Type {
+        signature: format!(
+          "pub type {name}{} = {};",
+          generics(&inner["generics"]),
+          render_type(&inner["type"])
+        ),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "constant" => Item::Constant {
+        signature: constant_signature(&name, inner),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "module" => Item::Module {
+        reexports: self.reexports(inner, &path),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+        items: self
+          .children(&inner["items"])
+          .into_iter()
+          .filter_map(|child| child["name"].as_str())
+          .map(str::to_string)
+          .collect(),
+      },
+      "union" => Item::Union {
+        signature: format!(
+          "pub union {name}{}{} {{ {} }}",
+          generics(&inner["generics"]),
+          where_clause(&inner["generics"]),
+          self.named_fields(&inner["fields"], true)
+        ),
+        fields: self.fields(&inner["fields"]),
+        implementations: self.implementations(&inner["impls"], &path),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "static" => Item::Static {
+        signature: format!(
+          "pub static {}{name}: {}",
+          if field(inner, &["is_mutable", "mutable"]).as_bool() == Some(true) {
+            "mut "
+          } else {
+            ""
+          },
+          render_type(&inner["type"])
+        ),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "trait_alias" => Item::TraitAlias {
+        signature: format!(
+          "pub trait {name}{} = {};",
+          generics(&inner["generics"]),
+          render_bounds(&inner["params"])
+        ),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "primitive" => Item::Primitive {
+        implementations: self.implementations(&inner["impls"], &path),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      "proc_macro" => match inner["kind"].as_str() {
+        Some("attr") => Item::AttributeMacro {
+          signature: format!("#[{name}]"),
+          name,
+          path,
+          description,
+          stability,
+          examples,
+          links,
+          aliases,
+          source,
+        },
+        Some("derive") => Item::DeriveMacro {
+          signature: format!("#[derive({name})]"),
+          helper_attributes: inner["helpers"]
+            .as_array()
+            .map(|helpers| {
+              helpers
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+            })
+            .unwrap_or_default(),
+          name,
+          path,
+          description,
+          stability,
+          examples,
+          links,
+          aliases,
+          source,
+        },
+        _ => Item::Macro {
+          signature: format!("{name}!() {{ /* proc-macro */ }}"),
+          name,
+          path,
+          description,
+          stability,
+          examples,
+          links,
+          aliases,
+          source,
+        },
+      },
+      "extern_type" | "foreign_type" => Item::ForeignType {
+        signature: format!("pub type {name};"),
+        implementations: Vec::new(),
+        name,
+        path,
+        description,
+        stability,
+        examples,
+        links,
+        aliases,
+        source,
+      },
+      _ => return None,
+    })
+  }
+
+  fn method(&self, item: &Value, parent: &str) -> Option<Item> {
+    let inner = &item["inner"]["function"];
+
+    if inner.is_null() {
+      return None;
+    }
+
+    let name = item["name"].as_str()?.to_string();
+
+    let signature = self.function_signature(item, inner);
+
+    Some(Item::Function {
+      path: format!("{parent}::{name}"),
+      parsed_signature: FunctionSignature::parse(&signature),
+      signature,
+      description: self.description(item),
+      stability: stability(item),
+      examples: examples(item),
+      links: self.links(item),
+      aliases: Vec::new(),
+      source: source(item),
+      name,
+    })
+  }
+
+  fn implementations(
+    &self,
+    impls: &Value,
+    parent: &str,
+  ) -> Vec<Implementation> {
+    self
+      .children(impls)
+      .into_iter()
+      .map(|item| &item["inner"]["impl"])
+      .filter(|implementation| !implementation.is_null())
+      .map(|implementation| {
+        let (trait_name, signature) = impl_header(implementation);
+
+        let kind = if field(implementation, &["is_synthetic", "synthetic"])
+          .as_bool()
+          == Some(true)
+        {
+          ImplementationKind::Auto
+        } else if !implementation["blanket_impl"].is_null() {
+          ImplementationKind::Blanket
+        } else if trait_name.is_some() {
+          ImplementationKind::Trait
+        } else {
+          ImplementationKind::Inherent
+        };
+
+        let (generics, where_clause) = impl_generics(&signature);
+
+        Implementation {
+          signature,
+          trait_name,
+          generics,
+          where_clause,
+          kind,
+          methods: self
+            .children(&implementation["items"])
+            .into_iter()
+            .filter_map(|item| self.method(item, parent))
+            .collect(),
+        }
+      })
+      .collect()
+  }
+
+  fn trait_methods(
+    &self,
+    inner: &Value,
+    parent: &str,
+    provided: bool,
+  ) -> Vec<Item> {
+    self
+      .children(&inner["items"])
+      .into_iter()
+      .filter(|child| {
+        child["inner"]["function"]["has_body"].as_bool() == Some(provided)
+      })
+      .filter_map(|child| self.method(child, parent))
+      .collect()
+  }
+
+  fn associated_types(&self, inner: &Value) -> Vec<AssociatedType> {
+    self
+      .children(&inner["items"])
+      .into_iter()
+      .filter_map(|child| {
+        let associated = child["inner"].get("assoc_type")?;
+
+        let name = child["name"].as_str()?.to_string();
+
+        let bounds = bound_list(&associated["bounds"]);
+
+        let default = match field(associated, &["type", "default"]) {
+          Value::Null => None,
+          ty => Some(render_type(ty)),
+        };
+
+        Some(AssociatedType {
+          signature: format!(
+            "type {name}{}{}{}{}",
+            generics(&associated["generics"]),
+            if bounds.is_empty() {
+              String::new()
+            } else {
+              format!(": {}", bounds.join(" + "))
+            },
+            where_clause(&associated["generics"]),
+            default
+              .as_ref()
+              .map(|default| format!(" = {default}"))
+              .unwrap_or_default()
+          ),
+          description: self.description(child),
+          name,
+          bounds,
+          default,
+        })
+      })
+      .collect()
+  }
+
+  fn associated_constants(&self, inner: &Value) -> Vec<AssociatedConstant> {
+    self
+      .children(&inner["items"])
+      .into_iter()
+      .filter_map(|child| {
+        let associated = child["inner"].get("assoc_const")?;
+
+        let name = child["name"].as_str()?.to_string();
+
+        let ty = render_type(&associated["type"]);
+
+        let default = field(associated, &["value", "default"])
+          .as_str()
+          .map(str::to_string);
+
+        Some(AssociatedConstant {
+          signature: format!(
+            "const {name}: {ty}{}",
+            default
+              .as_ref()
+              .map(|default| format!(" = {default}"))
+              .unwrap_or_default()
+          ),
+          description: self.description(child),
+          name,
+          ty,
+          default,
+        })
+      })
+      .collect()
+  }
+
+  fn implementors(&self, inner: &Value, path: &str) -> Vec<Implementor> {
+    let crate_name = path.split("::").next().unwrap_or_default();
+
+    self
+      .children(&inner["implementations"])
+      .into_iter()
+      .map(|item| &item["inner"]["impl"])
+      .filter(|implementation| {
+        !implementation.is_null()
+          && field(implementation, &["is_synthetic", "synthetic"]).as_bool()
+            != Some(true)
+      })
+      .map(|implementation| Implementor {
+        crate_name: crate_name.to_string(),
+        signature: impl_header(implementation).1,
+        ty: render_type(&implementation["for"]),
+      })
+      .collect()
+  }
+
+  fn struct_fields(&self, kind: &Value) -> Vec<Field> {
+    if let Some(fields) = kind.get("tuple") {
+      self.fields(fields)
+    } else if let Some(plain) = kind.get("plain") {
+      self.fields(&plain["fields"])
+    } else {
+      Vec::new()
+    }
+  }
+
+  fn fields(&self, ids: &Value) -> Vec<Field> {
+    self
+      .children(ids)
+      .into_iter()
+      .filter_map(|field| {
+        Some(Field {
+          name: field["name"].as_str()?.to_string(),
+          ty: render_type(&field["inner"]["struct_field"]),
+          visibility: match visibility_prefix(field).trim() {
+            "" => "private".to_string(),
+            visibility => visibility.to_string(),
+          },
+          description: self.description(field),
+        })
+      })
+      .collect()
+  }
+
+  fn variants(&self, inner: &Value) -> Vec<Variant> {
+    self
+      .children(&inner["variants"])
+      .into_iter()
+      .filter_map(|variant| {
+        let kind = &variant["inner"]["variant"]["kind"];
+
+        Some(Variant {
+          name: variant["name"].as_str()?.to_string(),
+          kind: if let Some(fields) = kind.get("tuple") {
+            VariantKind::Tuple(
+              fields
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|id| match self.get(id) {
+                  Some(field) => render_type(&field["inner"]["struct_field"]),
+                  None => "_".to_string(),
+                })
+                .collect(),
+            )
+          } else if let Some(fields) = kind.get("struct") {
+            VariantKind::Struct(
+              self
+                .fields(&fields["fields"])
+                .into_iter()
+                .map(|field| Field {
+                  visibility: "pub".to_string(),
+                  ..field
+                })
+                .collect(),
+            )
+          } else {
+            VariantKind::Unit
+          },
+          discriminant: variant["inner"]["variant"]["discriminant"]["expr"]
+            .as_str()
+            .map(str::to_string),
+          description: self.description(variant),
+        })
+      })
+      .collect()
+  }
+
+  fn variant_headers(&self, inner: &Value) -> Vec<String> {
+    self
+      .children(&inner["variants"])
+      .into_iter()
+      .filter_map(|variant| {
+        let name = variant["name"].as_str()?;
+
+        let kind = &variant["inner"]["variant"]["kind"];
+
+        Some(format!("{name}{}", self.variant_fields(kind)))
+      })
+      .collect()
+  }
+
+  fn variant_fields(&self, kind: &Value) -> String {
+    if let Some(fields) = kind.get("tuple") {
+      format!("({})", self.tuple_fields(fields, false))
+    } else if let Some(fields) = kind.get("struct") {
+      format!(" {{ {} }}", self.named_fields(&fields["fields"], false))
+    } else {
+      String::new()
+    }
+  }
+
+  fn tuple_fields(&self, fields: &Value, visibility: bool) -> String {
+    fields
+      .as_array()
+      .map(|fields| {
+        fields
+          .iter()
+          .map(|id| match self.get(id) {
+            Some(field) => format!(
+              "{}{}",
+              if visibility {
+                visibility_prefix(field)
+              } else {
+                ""
+              },
+              render_type(&field["inner"]["struct_field"])
+            ),
+            None => "_".to_string(),
+          })
+          .collect::<Vec<_>>()
+          .join(", ")
+      })
+      .unwrap_or_default()
+  }
+
+  fn named_fields(&self, fields: &Value, visibility: bool) -> String {
+    self
+      .children(fields)
+      .into_iter()
+      .filter_map(|field| {
+        Some(format!(
+          "{}{}: {}",
+          if visibility {
+            visibility_prefix(field)
+          } else {
+            ""
+          },
+          field["name"].as_str()?,
+          render_type(&field["inner"]["struct_field"])
+        ))
+      })
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  fn function_signature(&self, item: &Value, inner: &Value) -> String {
+    let header = &inner["header"];
+
+    let mut qualifiers = String::new();
+
+    if field(header, &["is_const", "const_"]).as_bool() == Some(true) {
+      qualifiers.push_str("const ");
+    }
+
+    if field(header, &["is_async", "async_"]).as_bool() == Some(true) {
+      qualifiers.push_str("async ");
+    }
+
+    if field(header, &["is_unsafe", "unsafe_"]).as_bool() == Some(true) {
+      qualifiers.push_str("unsafe ");
+    }
+
+    if let Some(abi) = abi(&header["abi"]) {
+      qualifiers.push_str(&format!("extern \"{abi}\" "));
+    }
+
+    let declaration = field(inner, &["sig", "decl"]);
+
+    let inputs = declaration["inputs"]
+      .as_array()
+      .map(|inputs| {
+        inputs
+          .iter()
+          .map(|input| render_input(&input[0], &input[1]))
+          .collect::<Vec<_>>()
+          .join(", ")
+      })
+      .unwrap_or_default();
+
+    let output = match &declaration["output"] {
+      Value::Null => String::new(),
+      output => format!(" -> {}", render_type(output)),
+    };
+
+    format!(
+      "{}{qualifiers}fn {}{}({inputs}){output}{}",
+      visibility_prefix(item),
+      item["name"].as_str().unwrap_or_default(),
+      generics(&inner["generics"]),
+      where_clause(&inner["generics"])
+    )
+  }
+
+  fn struct_signature(&self, name: &str, inner: &Value) -> String {
+    let kind = &inner["kind"];
+
+    let body = if let Some(fields) = kind.get("tuple") {
+      format!("({});", self.tuple_fields(fields, true))
+    } else if let Some(plain) = kind.get("plain") {
+      format!(" {{ {} }}", self.named_fields(&plain["fields"], true))
+    } else {
+      ";".to_string()
+    };
+
+    format!(
+      "pub struct {name}{}{}{body}",
+      generics(&inner["generics"]),
+      where_clause(&inner["generics"])
+    )
+  }
+
+  fn enum_signature(&self, name: &str, inner: &Value) -> String {
+    format!(
+      "pub enum {name}{}{} {{ {} }}",
+      generics(&inner["generics"]),
+      where_clause(&inner["generics"]),
+      self.variant_headers(inner).join(", ")
+    )
+  }
+}
+
+/// Render an impl block's header, returning the implemented trait separately.
+fn impl_header(implementation: &Value) -> (Option<String>, String) {
+  let trait_name = match &implementation["trait"] {
+    Value::Null => None,
+    path => Some(format!(
+      "{}{}",
+      if field(implementation, &["is_negative", "negative"]).as_bool()
+        == Some(true)
+      {
+        "!"
+      } else {
+        ""
+      },
+      render_path(path)
+    )),
+  };
+
+  let signature = format!(
+    "{}impl{} {}{}{}",
+    if field(implementation, &["is_unsafe", "unsafe_"]).as_bool() == Some(true)
+    {
+      "unsafe "
+    } else {
+      ""
+    },
+    generics(&implementation["generics"]),
+    trait_name
+      .as_ref()
+      .map(|name| format!("{name} for "))
+      .unwrap_or_default(),
+    render_type(&implementation["for"]),
+    where_clause(&implementation["generics"])
+  );
+
+  (trait_name, signature)
+}
+
+/// Deprecation comes from its own field, while feature gates, platform
+/// conditions and the versions std items were stabilized in are only
+/// available from the item's `cfg`, `unstable` and `stable` attributes.
+fn stability(item: &Value) -> Stability {
+  let attributes = item["attrs"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter_map(|attribute| {
+      attribute.as_str().or_else(|| attribute["other"].as_str())
+    })
+    .collect::<Vec<_>>();
+
+  let cfg = attributes
+    .iter()
+    .find_map(|attribute| attribute_cfg(attribute));
+
+  let feature = regex!(r#"feature\s*=\s*"([^"]+)""#);
+
+  let since = regex!(r#"since\s*=\s*"([^"]+)""#);
+
+  Stability {
+    deprecated: item["deprecation"].as_object().map(|deprecation| {
+      Deprecation {
+        since: deprecation
+          .get("since")
+          .and_then(Value::as_str)
+          .map(str::to_string),
+        note: deprecation
+          .get("note")
+          .and_then(Value::as_str)
+          .map(str::to_string),
+      }
+    }),
+    unstable: attributes
+      .iter()
+      .filter(|attribute| attribute.starts_with("#[unstable"))
+      .find_map(|attribute| {
+        feature
+          .captures(attribute)
+          .map(|captures| captures[1].to_string())
+      }),
+    required_features: cfg.as_deref().map(cfg_features).unwrap_or_default(),
+    cfg,
+    since: attributes
+      .iter()
+      .filter(|attribute| attribute.starts_with("#[stable"))
+      .find_map(|attribute| since.captures(attribute))
+      .map(|captures| captures[1].to_string()),
+    const_since: attributes.iter().find_map(|attribute| {
+      if attribute.starts_with("#[rustc_const_stable") {
+        since
+          .captures(attribute)
+          .map(|captures| captures[1].to_string())
+      } else {
+        attribute
+          .starts_with("#[rustc_const_unstable")
+          .then(|| "unstable".to_string())
+      }
+    }),
+  }
+}
+
+fn examples(item: &Value) -> Vec<Example> {
+  item["docs"]
+    .as_str()
+    .map(markdown_examples)
+    .unwrap_or_default()
+}
+
+/// The file and line range an item is defined at.
+fn source(item: &Value) -> Option<String> {
+  let span = &item["span"];
+
+  Some(format!(
+    "{}#{}-{}",
+    span["filename"].as_str()?,
+    span["begin"][0].as_u64()?,
+    span["end"][0].as_u64()?
+  ))
+}
+
+fn description(item: &Value) -> Option<String> {
+  item["docs"]
+    .as_str()
+    .map(|docs| docs.trim().to_string())
+    .filter(|docs| !docs.is_empty())
+}
+
+fn visibility_prefix(item: &Value) -> &'static str {
+  match &item["visibility"] {
+    Value::String(visibility) if visibility == "public" => "pub ",
+    Value::String(visibility) if visibility == "crate" => "pub(crate) ",
+    Value::Object(_) => "pub(restricted) ",
+    _ => "",
+  }
+}
+
+fn abi(abi: &Value) -> Option<String> {
+  match abi {
+    Value::String(abi) if abi == "Rust" => None,
+    Value::String(abi) => Some(abi.clone()),
+    Value::Object(abi) => abi.keys().next().cloned(),
+    _ => None,
+  }
+}
+
+fn trait_signature(name: &str, inner: &Value) -> String {
+  let bounds = render_bounds(&inner["bounds"]);
+
+  format!(
+    "pub {}{}trait {name}{}{}{}",
+    if inner["is_unsafe"].as_bool() == Some(true) {
+      "unsafe "
+    } else {
+      ""
+    },
+    if inner["is_auto"].as_bool() == Some(true) {
+      "auto "
+    } else {
+      ""
+    },
+    generics(&inner["generics"]),
+    if bounds.is_empty() {
+      String::new()
+    } else {
+      format!(": {bounds}")
+    },
+    where_clause(&inner["generics"])
+  )
+}
+
+fn constant_signature(name: &str, inner: &Value) -> String {
+  let value = field(inner, &["const", "const_"]);
+
+  let value = field(value, &["expr", "value"]).as_str().unwrap_or("_");
+
+  format!(
+    "pub const {name}: {} = {value};",
+    render_type(&inner["type"])
+  )
+}
+
+fn render_input(name: &Value, ty: &Value) -> String {
+  let name = name.as_str().unwrap_or("_");
+
+  if name == "self" {
+    match ty.get("borrowed_ref") {
+      Some(reference) if reference["type"]["generic"] == "Self" => {
+        return format!(
+          "&{}{}self",
+          reference["lifetime"]
+            .as_str()
+            .map(|lifetime| format!("{lifetime} "))
+            .unwrap_or_default(),
+          if field(reference, &["is_mutable", "mutable"]).as_bool()
+            == Some(true)
+          {
+            "mut "
+          } else {
+            ""
+          }
+        );
+      }
+      _ if ty["generic"] == "Self" => return "self".to_string(),
+      _ => {}
+    }
+  }
+
+  format!("{name}: {}", render_type(ty))
+}
+
+fn render_type(ty: &Value) -> String {
+  let Some((kind, inner)) = ty.as_object().and_then(|ty| ty.iter().next())
+  else {
+    return match ty {
+      Value::String(kind) if kind == "infer" => "_".to_string(),
+      _ => String::new(),
+    };
+  };
+
+  match kind.as_str() {
+    "resolved_path" => render_path(inner),
+    "generic" | "primitive" => inner.as_str().unwrap_or_default().to_string(),
+    "tuple" => {
+      let types = inner
+        .as_array()
+        .map(|types| types.iter().map(render_type).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+      if types.len() == 1 {
+        format!("({},)", types[0])
+      } else {
+        format!("({})", types.join(", "))
+      }
+    }
+    "slice" => format!("[{}]", render_type(inner)),
+    "array" => format!(
+      "[{}; {}]",
+      render_type(&inner["type"]),
+      inner["len"].as_str().unwrap_or("_")
+    ),
+    "borrowed_ref" => format!(
+      "&{}{}{}",
+      inner["lifetime"]
+        .as_str()
+        .map(|lifetime| format!("{lifetime} "))
+        .unwrap_or_default(),
+      if field(inner, &["is_mutable", "mutable"]).as_bool() == Some(true) {
+        "mut "
+      } else {
+        ""
+      },
+      render_type(&inner["type"])
+    ),
+    "raw_pointer" => format!(
+      "*{} {}",
+      if field(inner, &["is_mutable", "mutable"]).as_bool() == Some(true) {
+        "mut"
+      } else {
+        "const"
+      },
+      render_type(&inner["type"])
+    ),
+    "impl_trait" => format!("impl {}", render_bounds(inner)),
+    "dyn_trait" => {
+      let mut bounds = inner["traits"]
+        .as_array()
+        .map(|traits| {
+          traits
+            .iter()
+            .map(|poly| render_path(&poly["trait"]))
+            .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+      if let Some(lifetime) = inner["lifetime"].as_str() {
+        bounds.push(lifetime.to_string());
+      }
+
+      format!("dyn {}", bounds.join(" + "))
+    }
+    "qualified_path" => {
+      let self_type = render_type(&inner["self_type"]);
+
+      let name = inner["name"].as_str().unwrap_or_default();
+
+      match &inner["trait"] {
+        Value::Null => format!("{self_type}::{name}"),
+        path => format!("<{self_type} as {}>::{name}", render_path(path)),
+      }
+    }
+    "function_pointer" => {
+      let declaration = field(inner, &["sig", "decl"]);
+
+      let inputs = declaration["inputs"]
+        .as_array()
+        .map(|inputs| {
+          inputs
+            .iter()
+            .map(|input| render_type(&input[1]))
+            .collect::<Vec<_>>()
+            .join(", ")
+        })
+        .unwrap_or_default();
+
+      match &declaration["output"] {
+        Value::Null => format!("fn({inputs})"),
+        output => format!("fn({inputs}) -> {}", render_type(output)),
+      }
+    }
+    _ => String::new(),
+  }
+}
+
+fn render_path(path: &Value) -> String {
+  let name = field(path, &["path", "name"]).as_str().unwrap_or_default();
+
+  format!("{name}{}", render_generic_args(&path["args"]))
+}
+
+fn render_generic_args(args: &Value) -> String {
+  if let Some(angle) = args.get("angle_bracketed") {
+    let mut rendered = angle["args"]
+      .as_array()
+      .map(|args| {
+        args
+          .iter()
+          .map(
+            |arg| match arg.as_object().and_then(|arg| arg.iter().next()) {
+              Some((kind, value)) if kind == "lifetime" => {
+                value.as_str().unwrap_or_default().to_string()
+              }
+              Some((kind, value)) if kind == "type" => render_type(value),
+              Some((kind, value)) if kind == "const" => {
+                field(value, &["expr", "value"])
+                  .as_str()
+                  .unwrap_or("_")
+                  .to_string()
+              }
+              _ => "_".to_string(),
+            },
+          )
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    if let Some(constraints) =
+      field(angle, &["constraints", "bindings"]).as_array()
+    {
+      rendered.extend(constraints.iter().map(|constraint| {
+        let name = constraint["name"].as_str().unwrap_or_default();
+
+        match &constraint["binding"] {
+          binding if binding.get("equality").is_some() => {
+            let term = &binding["equality"];
+            format!(
+              "{name} = {}",
+              term
+                .get("type")
+                .map(render_type)
+                .unwrap_or_else(|| render_type(term))
+            )
+          }
+          binding => {
+            format!("{name}: {}", render_bounds(&binding["constraint"]))
+          }
+        }
+      }));
+    }
+
+    if rendered.is_empty() {
+      String::new()
+    } else {
+      format!("<{}>", rendered.join(", "))
+    }
+  } else if let Some(parenthesized) = args.get("parenthesized") {
+    let inputs = parenthesized["inputs"]
+      .as_array()
+      .map(|inputs| {
+        inputs
+          .iter()
+          .map(render_type)
+          .collect::<Vec<_>>()
+          .join(", ")
+      })
+      .unwrap_or_default();
+
+    match &parenthesized["output"] {
+      Value::Null => format!("({inputs})"),
+      output => format!("({inputs}) -> {}", render_type(output)),
+    }
+  } else {
+    String::new()
+  }
+}
+
+fn bound_list(bounds: &Value) -> Vec<String> {
+  bounds
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter_map(|bound| {
+      if let Some(trait_bound) = bound.get("trait_bound") {
+        let modifier = match trait_bound["modifier"].as_str() {
+          Some("maybe") => "?",
+          Some("maybe_const") => "~const ",
+          _ => "",
+        };
+
+        Some(format!("{modifier}{}", render_path(&trait_bound["trait"])))
+      } else {
+        bound["outlives"].as_str().map(str::to_string)
+      }
+    })
+    .collect()
+}
+
+fn render_bounds(bounds: &Value) -> String {
+  bound_list(bounds).join(" + ")
+}
+
+fn generics(generics: &Value) -> String {
+  let params = generics["params"]
+    .as_array()
+    .map(|params| {
+      params
+        .iter()
+        .filter(|param| {
+          field(&param["kind"]["type"], &["is_synthetic", "synthetic"])
+            .as_bool()
+            != Some(true)
+        })
+        .filter_map(|param| {
+          let name = param["name"].as_str()?;
+
+          let kind = &param["kind"];
+
+          Some(if let Some(ty) = kind.get("type") {
+            let bounds = render_bounds(&ty["bounds"]);
+
+            let mut rendered = name.to_string();
+
+            if !bounds.is_empty() {
+              rendered.push_str(&format!(": {bounds}"));
+            }
+
+            if !ty["default"].is_null() {
+              rendered.push_str(&format!(" = {}", render_type(&ty["default"])));
+            }
+
+            rendered
+          } else if let Some(constant) = kind.get("const") {
+            format!("const {name}: {}", render_type(&constant["type"]))
+          } else {
+            name.to_string()
+          })
+        })
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+
+  if params.is_empty() {
+    String::new()
+  } else {
+    format!("<{}>", params.join(", "))
+  }
+}
+
+fn where_clause(generics: &Value) -> String {
+  let predicates = generics["where_predicates"]
+    .as_array()
+    .map(|predicates| {
+      predicates
+        .iter()
+        .filter_map(|predicate| {
+          if let Some(bound) = predicate.get("bound_predicate") {
+            Some(format!(
+              "{}: {}",
+              render_type(&bound["type"]),
+              render_bounds(&bound["bounds"])
+            ))
+          } else if let Some(lifetime) = predicate.get("lifetime_predicate") {
+            Some(format!(
+              "{}: {}",
+              lifetime["lifetime"].as_str()?,
+              lifetime["outlives"]
+                .as_array()?
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" + ")
+            ))
+          } else {
+            predicate.get("eq_predicate").map(|equality| {
+              format!(
+                "{} = {}",
+                render_type(&equality["lhs"]),
+                render_type(
+                  equality["rhs"].get("type").unwrap_or(&equality["rhs"])
+                )
+              )
+            })
+          }
+        })
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+
+  if predicates.is_empty() {
+    String::new()
+  } else {
+    format!(" where {}", predicates.join(", "))
+  }
+}
@@ -0,0 +1,281 @@
+use {super::*, serde_json::Value};
+
+/// One-line item descriptions for a crate, keyed by fully qualified path
+/// (e.g. `tokio::sync::mpsc::Sender`).
+pub type Descriptions = HashMap<String, String>;
+
+/// What a crate's entry in `search-index.js` records about it.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+  pub descriptions: Descriptions,
+  /// The items with a page of their own, starting with the crate root, or
+  /// `None` when the index uses an item type encoding this doesn't know.
+  pub entries: Option<Vec<Entry>>,
+}
+
+pub fn load_search_index(
+  root: &Path,
+  crate_name: &str,
+) -> Result<Option<SearchIndex>> {
+  let path = root.join("search-index.js");
+
+  if !path.is_file() {
+    return Ok(None);
+  }
+
+  let Some(data) = crate_data(&fs::read_to_string(&path)?, crate_name)? else {
+    return Ok(None);
+  };
+
+  let names = data["n"]
+    .as_array()
+    .map(|names| {
+      names
+        .iter()
+        .map(|name| name.as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+
+  let paths = module_paths(&data["q"], names.len(), crate_name);
+
+  let entries = data["t"].as_str().map(|types| {
+    let crate_path = root.join(crate_name);
+
+    let root_entry = Entry {
+      aliases: Vec::new(),
+      description: None,
+      file: crate_path.join("index.html"),
+      kind: ItemKind::Module,
+      module: String::new(),
+      name: crate_name.to_string(),
+    };
+
+    iter::once(root_entry)
+      .chain(names.iter().zip(&paths).zip(types.chars()).filter_map(
+        |((name, module), ty)| {
+          let kind = item_kind(ty)?;
+
+          let directory = module
+            .split("::")
+            .skip(1)
+            .fold(crate_path.clone(), |directory, segment| {
+              directory.join(segment)
+            });
+
+          let file = match kind.prefix() {
+            Some(prefix) => directory.join(format!("{prefix}.{name}.html")),
+            None => directory.join(name).join("index.html"),
+          };
+
+          Some(Entry {
+            aliases: Vec::new(),
+            description: None,
+            file,
+            kind,
+            module: module.clone(),
+            name: name.clone(),
+          })
+        },
+      ))
+      .collect()
+  });
+
+  let descriptions = match data["d"].as_array() {
+    Some(descriptions) => descriptions
+      .iter()
+      .map(|description| description.as_str().unwrap_or_default().to_string())
+      .collect(),
+    None => load_description_shards(root, crate_name)?,
+  };
+
+  let descriptions = names.iter().zip(paths).zip(descriptions).fold(
+    HashMap::new(),
+    |mut acc, ((name, path), description)| {
+      let description = html_to_text(description);
+
+      if !name.is_empty() && !description.is_empty() {
+        acc.entry(format!("{path}::{name}")).or_insert(description);
+      }
+
+      acc
+    },
+  );
+
+  Ok(Some(SearchIndex {
+    descriptions,
+    entries,
+  }))
+}
+
+/// The kind of page an item type in the index's `t` column has, which rustdoc
+/// encodes as a letter counting up from `A`. Methods, fields, variants, impls
+/// and re-exports are documented on other pages and have none.
+fn item_kind(ty: char) -> Option<ItemKind> {
+  Some(match u32::from(ty).checked_sub(u32::from('A'))? {
+    0 => ItemKind::Keyword,
+    1 => ItemKind::Primitive,
+    2 => ItemKind::Module,
+    5 => ItemKind::Struct,
+    6 => ItemKind::Enum,
+    7 => ItemKind::Function,
+    8 => ItemKind::Type,
+    9 => ItemKind::Static,
+    10 => ItemKind::Trait,
+    16 => ItemKind::Macro,
+    18 => ItemKind::Constant,
+    20 => ItemKind::Union,
+    21 => ItemKind::ForeignType,
+    23 => ItemKind::AttributeMacro,
+    24 => ItemKind::DeriveMacro,
+    25 => ItemKind::TraitAlias,
+    _ => return None,
+  })
+}
+
+/// Extract the per-crate object from a `search-index.js` file, which wraps a
+/// JSON array of `[crate, data]` pairs in a single-quoted JavaScript string.
+fn crate_data(script: &str, crate_name: &str) -> Result<Option<Value>> {
+  let Some(start) = script.find("JSON.parse('") else {
+    return Ok(None);
+  };
+
+  let json = unescape_js_string(&script[start + "JSON.parse('".len()..]);
+
+  let crates = serde_json::from_str::<Value>(&json)?;
+
+  Ok(
+    crates
+      .as_array()
+      .into_iter()
+      .flatten()
+      .find(|entry| entry[0].as_str() == Some(crate_name))
+      .map(|entry| entry[1].clone()),
+  )
+}
+
+/// Expand rustdoc's compressed module path column. Older versions store one
+/// string per item with empty strings meaning "same as previous", newer
+/// versions store sparse `[index, path]` pairs.
+fn module_paths(paths: &Value, len: usize, crate_name: &str) -> Vec<String> {
+  let mut expanded = Vec::with_capacity(len);
+
+  let mut current = crate_name.to_string();
+
+  let sparse = paths
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| {
+      Some((entry[0].as_u64()? as usize, entry[1].as_str()?.to_string()))
+    })
+    .collect::<HashMap<usize, String>>();
+
+  let dense = paths
+    .as_array()
+    .into_iter()
+    .flatten()
+    .filter_map(Value::as_str)
+    .collect::<Vec<_>>();
+
+  for index in 0..len {
+    if let Some(path) = sparse.get(&index) {
+      current.clone_from(path);
+    } else if let Some(path) = dense.get(index).filter(|path| !path.is_empty())
+    {
+      current = path.to_string();
+    }
+
+    expanded.push(current.clone());
+  }
+
+  expanded
+}
+
+fn load_description_shards(
+  root: &Path,
+  crate_name: &str,
+) -> Result<Vec<String>> {
+  let directory = root.join("search.desc").join(crate_name);
+
+  if !directory.is_dir() {
+    return Ok(Vec::new());
+  }
+
+  let prefix = format!("{crate_name}-desc-");
+
+  let mut shards = fs::read_dir(&directory)?
+    .filter_map(|entry| entry.ok())
+    .filter_map(|entry| {
+      let file_name = entry.file_name().to_str()?.to_string();
+
+      let shard = file_name
+        .strip_prefix(&prefix)?
+        .split('-')
+        .next()?
+        .parse::<usize>()
+        .ok()?;
+
+      Some((shard, entry.path()))
+    })
+    .collect::<Vec<_>>();
+
+  shards.sort();
+
+  shards
+    .into_iter()
+    .try_fold(Vec::new(), |mut acc, (_, path)| {
+      acc.extend(parse_description_shard(&fs::read_to_string(path)?)?);
+      Ok(acc)
+    })
+}
+
+/// Parse a `searchState.loadedDescShard("crate", 0, "[...]")` call, whose
+/// last argument is a double-quoted JavaScript string holding a JSON array.
+fn parse_description_shard(script: &str) -> Result<Vec<String>> {
+  let arguments = script
+    .split_once('(')
+    .and_then(|(_, rest)| rest.rsplit_once(')'))
+    .map(|(arguments, _)| arguments)
+    .ok_or_else(|| anyhow!("malformed search description shard"))?;
+
+  let literal = arguments
+    .splitn(3, ',')
+    .nth(2)
+    .map(str::trim)
+    .ok_or_else(|| anyhow!("malformed search description shard"))?;
+
+  let json = serde_json::from_str::<String>(literal)?;
+
+  Ok(serde_json::from_str::<Vec<String>>(&json)?)
+}
+
+/// Unescape a single-quoted JavaScript string literal, stopping at its
+/// closing quote.
+fn unescape_js_string(string: &str) -> String {
+  let mut unescaped = String::with_capacity(string.len());
+
+  let mut chars = string.chars();
+
+  while let Some(c) = chars.next() {
+    if c == '\'' {
+      break;
+    }
+
+    if c != '\\' {
+      unescaped.push(c);
+      continue;
+    }
+
+    match chars.next() {
+      Some('\n') | None => {}
+      Some(escaped @ ('\\' | '\'')) => unescaped.push(escaped),
+      Some(other) => {
+        unescaped.push('\\');
+        unescaped.push(other);
+      }
+    }
+  }
+
+  unescaped
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+/// How a change to a crate's API would likely have to be released: `Major`
+/// can break downstream code, `Minor` only adds to the API, and `Patch`
+/// leaves it untouched.
+#[derive(
+  Clone,
+  Copy,
+  Debug,
+  Default,
+  Deserialize,
+  Eq,
+  Ord,
+  PartialEq,
+  PartialOrd,
+  Serialize,
+)]
+pub enum SemverImpact {
+  #[default]
+  Patch,
+  Minor,
+  Major,
+}
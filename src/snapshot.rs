@@ -0,0 +1,72 @@
+use super::*;
+
+pub const SNAPSHOT_DIRECTORY: &str = "snapshots";
+
+/// A named copy of crates' items and versions, saved in the snapshot
+/// directory so that the API can be compared against after regenerating it.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+  directory: PathBuf,
+  name: String,
+}
+
+impl Snapshot {
+  pub fn new(snapshots: &Path, name: &str) -> Result<Self> {
+    if !regex!(r"^[A-Za-z0-9][A-Za-z0-9._-]*$").is_match(name) {
+      return Err(anyhow!("invalid snapshot name '{name}'").into());
+    }
+
+    Ok(Self {
+      directory: snapshots.join(name),
+      name: name.to_string(),
+    })
+  }
+
+  pub fn exists(&self) -> bool {
+    self.directory.is_dir()
+  }
+
+  pub fn crates(&self) -> Result<Vec<String>> {
+    let mut crates = fs::read_dir(&self.directory)?
+      .filter_map(|entry| entry.ok())
+      .map(|entry| entry.path())
+      .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+      .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+      .collect::<Vec<_>>();
+
+    crates.sort();
+
+    Ok(crates)
+  }
+
+  pub fn load(&self, crate_name: &str) -> Result<Documentation> {
+    let path = self.directory.join(format!("{crate_name}.json"));
+
+    if !path.is_file() {
+      return Err(
+        anyhow!("crate '{crate_name}' is not in snapshot '{}'", self.name)
+          .into(),
+      );
+    }
+
+    serde_json::from_str(&fs::read_to_string(path)?).map_err(|error| {
+      anyhow!(
+        "failed to read '{crate_name}' from snapshot '{}', which may predate \
+         this version of crates-mcp and need saving again: {error}",
+        self.name
+      )
+      .into()
+    })
+  }
+
+  pub fn save(&self, documentation: &Documentation) -> Result {
+    fs::create_dir_all(&self.directory)?;
+
+    fs::write(
+      self.directory.join(format!("{}.json", documentation.name)),
+      serde_json::to_string(documentation)?,
+    )?;
+
+    Ok(())
+  }
+}
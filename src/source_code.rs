@@ -0,0 +1,12 @@
+use super::*;
+
+/// Lines of source code, either the definition of an item or a range of the
+/// file it is defined in.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SourceCode {
+  pub path: String,
+  pub file: String,
+  pub start_line: usize,
+  pub end_line: usize,
+  pub code: String,
+}
@@ -0,0 +1,17 @@
+use super::*;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Stability {
+  pub deprecated: Option<Deprecation>,
+  pub unstable: Option<String>,
+  pub required_features: Vec<String>,
+  pub cfg: Option<String>,
+  pub since: Option<String>,
+  pub const_since: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Deprecation {
+  pub since: Option<String>,
+  pub note: Option<String>,
+}
@@ -1,22 +1,40 @@
-use {super::*, lookup::Lookup};
+use {
+  super::*, diff::Diff, implementors::Implementors, lookup::Lookup,
+  overview::Overview, snapshot::Snapshot, source::Source,
+};
 
+mod diff;
+mod implementors;
 mod list;
 mod lookup;
+mod overview;
 mod server;
+mod snapshot;
+mod source;
 
 #[derive(Debug, Parser)]
 pub enum Subcommand {
+  Diff(Diff),
+  Implementors(Implementors),
   List,
   Lookup(Lookup),
+  Overview(Overview),
   Server,
+  Snapshot(Snapshot),
+  Source(Source),
 }
 
 impl Subcommand {
-  pub async fn run(self) -> Result {
+  pub async fn run(self, options: Options) -> Result {
     match self {
-      Self::List => list::run().await,
-      Self::Lookup(lookup) => lookup.run().await,
-      Self::Server => server::run().await,
+      Self::Diff(diff) => diff.run(options).await,
+      Self::Implementors(implementors) => implementors.run(options).await,
+      Self::List => list::run(options).await,
+      Self::Lookup(lookup) => lookup.run(options).await,
+      Self::Overview(overview) => overview.run(options).await,
+      Self::Server => server::run(options).await,
+      Self::Snapshot(snapshot) => snapshot.run(options).await,
+      Self::Source(source) => source.run(options).await,
     }
   }
 }
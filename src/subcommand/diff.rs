@@ -0,0 +1,40 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub struct Diff {
+  #[clap(help = "Snapshot name or documentation directory to compare from")]
+  old: String,
+  #[clap(help = "Snapshot name or documentation directory to compare to")]
+  new: String,
+  #[clap(
+    long = "crate",
+    value_name = "NAME",
+    help = "Only compare this crate (default: every crate found on both sides)"
+  )]
+  name: Option<String>,
+}
+
+impl From<Diff> for DiffDocsRequest {
+  fn from(value: Diff) -> Self {
+    DiffDocsRequest {
+      old: value.old,
+      new: Some(value.new),
+      name: value.name,
+    }
+  }
+}
+
+impl Diff {
+  pub async fn run(self, options: Options) -> Result {
+    let diffs = diff_docs(
+      &self.into(),
+      options.doc_path(),
+      &options.snapshot_dir(),
+      &options.cache(),
+    )?;
+
+    println!("{}", serde_json::to_string_pretty(&diffs)?.trim());
+
+    Ok(())
+  }
+}
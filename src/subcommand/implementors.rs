@@ -0,0 +1,19 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub struct Implementors {
+  #[clap(help = "Path of the trait (e.g., 'serde::Serialize')")]
+  trait_path: String,
+}
+
+impl Implementors {
+  pub async fn run(self, options: Options) -> Result {
+    let implementors = find_implementors(
+      options.doc_path(),
+      options.std_doc_path(),
+      &self.trait_path,
+    )?;
+    println!("{}", serde_json::to_string_pretty(&implementors)?.trim());
+    Ok(())
+  }
+}
@@ -1,7 +1,10 @@
 use super::*;
 
-pub async fn run() -> Result {
-  let crates = list_crates(DOC_PATH)?.join("\n");
+pub async fn run(options: Options) -> Result {
+  let crates = format_crate_targets(&list_crate_targets(
+    options.doc_path(),
+    options.std_doc_path(),
+  )?);
   println!("{crates}");
   Ok(())
 }
@@ -11,7 +11,7 @@ pub struct Lookup {
   #[clap(
     short = 't',
     long,
-    help = "Filter by item type: function, struct, enum, trait, macro, type, constant, module"
+    help = "Filter by item type: function, struct, enum, trait, macro, type, constant, module, union, static, trait_alias, primitive, keyword, attribute_macro, derive_macro, foreign_type"
   )]
   item_type: Option<String>,
   #[clap(
@@ -20,6 +20,25 @@ pub struct Lookup {
     help = "Search term to filter items by name or description"
   )]
   query: Option<String>,
+  #[clap(
+    short,
+    long,
+    help = "Only return items under this module path (e.g., 'tokio::sync')"
+  )]
+  path: Option<String>,
+  #[clap(
+    long,
+    help = "Omit auto-trait and blanket implementations from types"
+  )]
+  exclude_auto_impls: bool,
+  #[clap(long, help = "Omit deprecated items")]
+  exclude_deprecated: bool,
+  #[clap(long, help = "Only return items gated behind this crate feature")]
+  feature: Option<String>,
+  #[clap(long, help = "Return descriptions as plain text instead of Markdown")]
+  plain_text: bool,
+  #[clap(long, help = "Target triple whose docs to read (default: host)")]
+  target: Option<String>,
 }
 
 impl From<Lookup> for LookupCrateRequest {
@@ -30,13 +49,26 @@ impl From<Lookup> for LookupCrateRequest {
       offset: value.offset,
       item_type: value.item_type,
       query: value.query,
+      path: value.path,
+      exclude_auto_impls: value.exclude_auto_impls.then_some(true),
+      exclude_deprecated: value.exclude_deprecated.then_some(true),
+      feature: value.feature,
+      plain_text: value.plain_text.then_some(true),
+      target: value.target,
     }
   }
 }
 
 impl Lookup {
-  pub async fn run(self) -> Result {
-    let documentation = lookup_crate(&self.into(), DOC_PATH)?;
+  pub async fn run(self, options: Options) -> Result {
+    let request = LookupCrateRequest::from(self);
+
+    let documentation = lookup_crate(
+      &request,
+      options.doc_path_for(&request.name),
+      &options.cache(),
+    )?;
+
     println!("{}", serde_json::to_string_pretty(&documentation)?.trim());
     Ok(())
   }
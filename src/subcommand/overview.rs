@@ -0,0 +1,34 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub struct Overview {
+  #[clap(help = "The name of the crate")]
+  name: String,
+  #[clap(
+    long,
+    help = "Return the crate's docs as plain text instead of Markdown"
+  )]
+  plain_text: bool,
+  #[clap(long, help = "Target triple whose docs to read (default: host)")]
+  target: Option<String>,
+}
+
+impl Overview {
+  pub async fn run(self, options: Options) -> Result {
+    let request = CrateOverviewRequest {
+      name: self.name,
+      plain_text: self.plain_text.then_some(true),
+      target: self.target,
+    };
+
+    let overview = crate_overview(
+      &request,
+      options.doc_path_for(&request.name),
+      &options.cache(),
+    )?;
+
+    println!("{}", serde_json::to_string_pretty(&overview)?.trim());
+
+    Ok(())
+  }
+}
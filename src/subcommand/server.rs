@@ -1,9 +1,21 @@
 use super::*;
 
-pub async fn run() -> Result {
+pub async fn run(options: Options) -> Result {
   info!("Starting MCP server...");
-  let server = Router::new();
+
+  let server = Router::new(options.clone());
+
+  let log_level = server.log_level();
+
   let service = server.serve(stdio()).await?;
+
+  let path = options.doc_path().to_string();
+
+  let _watcher = watch(options, service.peer().clone(), log_level)
+    .inspect_err(|error| warn!("failed to watch {path}: {error}"))
+    .ok();
+
   service.waiting().await?;
+
   Ok(())
 }
@@ -0,0 +1,37 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub struct Snapshot {
+  #[clap(help = "Name to save the snapshot under")]
+  name: String,
+  #[clap(
+    long = "crate",
+    value_name = "NAME",
+    help = "Crate to save, may be repeated (default: every documented crate)"
+  )]
+  crates: Vec<String>,
+}
+
+impl From<Snapshot> for SaveSnapshotRequest {
+  fn from(value: Snapshot) -> Self {
+    SaveSnapshotRequest {
+      name: value.name,
+      crates: (!value.crates.is_empty()).then_some(value.crates),
+    }
+  }
+}
+
+impl Snapshot {
+  pub async fn run(self, options: Options) -> Result {
+    let crates = save_snapshot(
+      &self.into(),
+      options.doc_path(),
+      &options.snapshot_dir(),
+      &options.cache(),
+    )?;
+
+    println!("{}", crates.join("\n"));
+
+    Ok(())
+  }
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub struct Source {
+  #[clap(help = "Path of the item (e.g., 'tokio::sync::Mutex')")]
+  path: String,
+  #[clap(long, help = "Print the whole file the item is defined in")]
+  whole_file: bool,
+  #[clap(long, help = "First line of the file to print")]
+  start_line: Option<usize>,
+  #[clap(long, help = "Last line of the file to print")]
+  end_line: Option<usize>,
+  #[clap(long, help = "Target triple whose docs to read (default: host)")]
+  target: Option<String>,
+}
+
+impl From<Source> for GetSourceRequest {
+  fn from(value: Source) -> Self {
+    GetSourceRequest {
+      path: value.path,
+      whole_file: value.whole_file.then_some(true),
+      start_line: value.start_line,
+      end_line: value.end_line,
+      target: value.target,
+    }
+  }
+}
+
+impl Source {
+  pub async fn run(self, options: Options) -> Result {
+    let request = GetSourceRequest::from(self);
+
+    let crate_name = request.path.split("::").next().unwrap_or_default();
+
+    let source = get_source(
+      &request,
+      options.doc_path_for(crate_name),
+      options.workspace_root(),
+      &options.cache(),
+    )?;
+
+    println!("{}", serde_json::to_string_pretty(&source)?.trim());
+    Ok(())
+  }
+}
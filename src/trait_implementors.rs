@@ -0,0 +1,190 @@
+use {super::*, serde_json::Value};
+
+const IMPLEMENTOR_DIRECTORIES: [&str; 2] = ["trait.impl", "implementors"];
+
+/// Find every documented implementation of a trait, merging the impls listed
+/// on the trait's own page with the per-crate `trait.impl/` (or, on older
+/// rustdoc versions, `implementors/`) scripts that downstream crates add to.
+/// Both are read from the host tree at `path`, the per-target trees next to
+/// it and the standard library's docs at `std_path`.
+pub fn find_implementors(
+  path: &str,
+  std_path: Option<&str>,
+  trait_path: &str,
+) -> Result<Vec<Implementor>> {
+  let segments = trait_path
+    .trim_end_matches("::")
+    .split("::")
+    .collect::<Vec<_>>();
+
+  let (Some(crate_name), Some(name)) = (segments.first(), segments.last())
+  else {
+    return Err(anyhow!("invalid trait path '{trait_path}'").into());
+  };
+
+  let host = PathBuf::from(path);
+
+  let roots = iter::once(host.clone())
+    .chain(list_targets(&host).into_iter().map(|(_, root)| root))
+    .chain(std_path.map(PathBuf::from));
+
+  let mut implementors = Vec::new();
+
+  for root in roots {
+    implementors.extend(root_implementors(
+      &root,
+      crate_name,
+      name,
+      &segments[..segments.len() - 1],
+    )?);
+  }
+
+  implementors.sort_by(|a, b| {
+    (&a.crate_name, &a.signature).cmp(&(&b.crate_name, &b.signature))
+  });
+
+  implementors.dedup();
+
+  Ok(implementors)
+}
+
+/// The implementors of the trait `name` from `crate_name` documented in the
+/// tree at `root`.
+fn root_implementors(
+  root: &Path,
+  crate_name: &str,
+  name: &str,
+  module: &[&str],
+) -> Result<Vec<Implementor>> {
+  let mut modules = HashSet::new();
+
+  collect_trait_modules(
+    &root.join(crate_name),
+    &format!("trait.{name}.html"),
+    &mut modules,
+    &[crate_name.to_string()],
+  )?;
+
+  for directory in IMPLEMENTOR_DIRECTORIES {
+    collect_trait_modules(
+      &root.join(directory).join(crate_name),
+      &format!("trait.{name}.js"),
+      &mut modules,
+      &[crate_name.to_string()],
+    )?;
+  }
+
+  // Prefer the trait's defining module, but fall back to every trait with
+  // this name in the crate so re-exported paths like `serde::Serialize` work.
+  if modules.iter().any(|candidate| candidate == module) {
+    modules.retain(|candidate| candidate == module);
+  }
+
+  let mut implementors = Vec::new();
+
+  for module in modules {
+    let relative = module.iter().collect::<PathBuf>();
+
+    let page = root.join(&relative).join(format!("trait.{name}.html"));
+
+    if page.is_file() {
+      implementors.extend(extract_implementors(
+        &Html::parse_document(&fs::read_to_string(page)?),
+        crate_name,
+      ));
+    }
+
+    for directory in IMPLEMENTOR_DIRECTORIES {
+      let script = root
+        .join(directory)
+        .join(&relative)
+        .join(format!("trait.{name}.js"));
+
+      if script.is_file() {
+        implementors
+          .extend(parse_implementors_script(&fs::read_to_string(script)?)?);
+      }
+    }
+  }
+
+  Ok(implementors)
+}
+
+fn collect_trait_modules(
+  directory: &Path,
+  file_name: &str,
+  modules: &mut HashSet<Vec<String>>,
+  module: &[String],
+) -> Result {
+  if !directory.is_dir() {
+    return Ok(());
+  }
+
+  if directory.join(file_name).is_file() {
+    modules.insert(module.to_vec());
+  }
+
+  for entry in fs::read_dir(directory)? {
+    let path = entry?.path();
+
+    if let Some(child) = path.file_name().and_then(|name| name.to_str())
+      && path.is_dir()
+    {
+      collect_trait_modules(
+        &path,
+        file_name,
+        modules,
+        &[module, &[child.to_string()]].concat(),
+      )?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Parse an implementors script. Current rustdoc versions emit
+/// `Object.fromEntries([["crate", [[html, ...], ...]], ...])`, older ones a
+/// `var implementors = {"crate": [...]}` object literal.
+fn parse_implementors_script(script: &str) -> Result<Vec<Implementor>> {
+  let crates = ["Object.fromEntries(", "implementors = "]
+    .iter()
+    .find_map(|marker| {
+      let start = script.find(marker)? + marker.len();
+
+      serde_json::Deserializer::from_str(&script[start..])
+        .into_iter::<Value>()
+        .next()
+    })
+    .transpose()?
+    .ok_or_else(|| anyhow!("malformed implementors script"))?;
+
+  let crates = match crates {
+    Value::Array(pairs) => pairs
+      .into_iter()
+      .filter_map(|pair| Some((pair[0].as_str()?.to_string(), pair[1].clone())))
+      .collect::<Vec<_>>(),
+    Value::Object(map) => map.into_iter().collect(),
+    _ => Vec::new(),
+  };
+
+  Ok(
+    crates
+      .into_iter()
+      .flat_map(|(crate_name, entries)| {
+        entries
+          .as_array()
+          .into_iter()
+          .flatten()
+          .filter_map(|entry| {
+            let html = entry[0]
+              .as_str()
+              .or_else(|| entry["text"].as_str())
+              .or_else(|| entry.as_str())?;
+
+            implementor(&crate_name, &html_to_text(html.to_string()))
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect(),
+  )
+}
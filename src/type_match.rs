@@ -0,0 +1,10 @@
+use super::*;
+
+/// A function or method found by a type search, and whether its types are
+/// exactly those of the query.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct TypeMatch {
+  pub path: String,
+  pub signature: String,
+  pub exact: bool,
+}
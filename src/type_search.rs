@@ -0,0 +1,536 @@
+use super::*;
+
+/// How many matches are returned when the request doesn't set a limit.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Bounds that say nothing about what a generic parameter is for.
+const MARKER_BOUNDS: [&str; 8] = [
+  "?Sized", "Sized", "Send", "Sync", "Unpin", "Copy", "Clone", "Debug",
+];
+
+/// A type reduced to what type search compares.
+#[derive(Clone, Debug)]
+enum TypeTree {
+  /// A generic parameter, `impl Trait` or associated type, which stands for
+  /// any type that satisfies its bounds.
+  Generic(Vec<TypeTree>),
+  /// A type by the last segment of its path, with its generic arguments.
+  /// Slices and arrays are named `[]` and tuples `()`, while references,
+  /// pointers, lifetimes and `dyn` are dropped.
+  Named(String, Vec<TypeTree>),
+}
+
+/// The generic parameters in scope for a function, with their bounds, and
+/// what `Self` refers to.
+#[derive(Clone, Debug, Default)]
+struct Scope {
+  generics: HashMap<String, Vec<String>>,
+  self_type: Option<TypeTree>,
+}
+
+/// Find functions and methods by the types in their signatures, as in
+/// rustdoc's own search. A query like `&str -> Url` matches parameter types
+/// on the left, in any order, and the return type on the right; either side
+/// may be empty, and without an arrow every type only has to appear somewhere
+/// in the signature. References and paths are ignored, generic parameters and
+/// `impl Trait` match whatever satisfies their bounds, and a type also matches
+/// types it is nested in, such as `Result<Url, ParseError>`. Exact matches
+/// come first, followed by ever looser ones.
+pub fn search_by_type(
+  request: &SearchByTypeRequest,
+  path: &str,
+  std_path: Option<&str>,
+  cache: &Cache,
+) -> Result<Vec<TypeMatch>> {
+  let (inputs, output) = split_arrow(&request.query);
+
+  // Like rustdoc, single letters such as `T` stand for any type.
+  let scope = Scope {
+    generics: regex!(r"\b[A-Z][0-9]?\b")
+      .find_iter(&request.query)
+      .map(|name| (name.as_str().to_string(), Vec::new()))
+      .collect(),
+    ..Default::default()
+  };
+
+  let query = |ty: &str| (normalize(ty), parse_type(ty, &scope));
+
+  let inputs = split_top_level(inputs, ',')
+    .into_iter()
+    .map(query)
+    .collect::<Vec<_>>();
+
+  let output = output.map(|output| match output.trim() {
+    "" => query("()"),
+    output => query(output),
+  });
+
+  if inputs.is_empty() && output.is_none() {
+    return Err(anyhow!("type query '{}' is empty", request.query).into());
+  }
+
+  let mut matches = Vec::new();
+
+  for (lookup, path) in crate_lookups(request.name.as_deref(), path, std_path)?
+  {
+    let documentation = match lookup_crate(&lookup, path, cache) {
+      Ok(documentation) => documentation,
+      Err(error) => {
+        warn!("skipping type search for '{}': {error}", lookup.name);
+        continue;
+      }
+    };
+
+    for item in &documentation.items {
+      for (function, scope) in functions(item) {
+        let Item::Function {
+          path,
+          signature,
+          parsed_signature: Some(parsed_signature),
+          ..
+        } = function
+        else {
+          continue;
+        };
+
+        if let Some((score, unmatched)) =
+          score_function(parsed_signature, scope, &inputs, output.as_ref())
+        {
+          matches.push((
+            score,
+            unmatched,
+            TypeMatch {
+              path: path.clone(),
+              signature: signature.clone(),
+              exact: score == 0 && unmatched == 0,
+            },
+          ));
+        }
+      }
+    }
+  }
+
+  matches.sort_by(|(a_score, a_unmatched, a), (b_score, b_unmatched, b)| {
+    (a_score, a_unmatched, a.path.len(), &a.path).cmp(&(
+      b_score,
+      b_unmatched,
+      b.path.len(),
+      &b.path,
+    ))
+  });
+
+  Ok(
+    matches
+      .into_iter()
+      .map(|(_, _, type_match)| type_match)
+      .take(request.limit.unwrap_or(DEFAULT_LIMIT))
+      .collect(),
+  )
+}
+
+/// Free functions, and the methods of types and traits along with what
+/// `Self` and the impl's generic parameters mean for them. Methods of
+/// auto-trait and blanket impls are left out, since every type has them.
+fn functions(item: &Item) -> Vec<(&Item, Scope)> {
+  match item {
+    Item::Function { .. } => vec![(item, Scope::default())],
+    Item::Trait {
+      name,
+      required_methods,
+      provided_methods,
+      ..
+    } => {
+      let scope = Scope {
+        self_type: Some(TypeTree::Generic(vec![TypeTree::Named(
+          name.clone(),
+          Vec::new(),
+        )])),
+        ..Default::default()
+      };
+
+      required_methods
+        .iter()
+        .chain(provided_methods)
+        .map(|method| (method, scope.clone()))
+        .collect()
+    }
+    _ => item
+      .implementations()
+      .iter()
+      .filter(|implementation| !implementation.kind.is_synthetic())
+      .flat_map(|implementation| {
+        let (_, _, self_type, _) =
+          split_impl_header(&implementation.signature).unwrap_or_default();
+
+        let mut scope = Scope {
+          generics: bounds(
+            &implementation.generics,
+            &implementation.where_clause,
+          ),
+          ..Default::default()
+        };
+
+        scope.self_type = Some(parse_type(self_type, &scope));
+
+        implementation
+          .methods
+          .iter()
+          .map(move |method| (method, scope.clone()))
+      })
+      .collect(),
+  }
+}
+
+/// The bounds of each generic type parameter, whether declared inline or in a
+/// `where` clause.
+fn bounds(
+  generics: &[GenericParameter],
+  where_clause: &[WherePredicate],
+) -> HashMap<String, Vec<String>> {
+  let mut bounds = generics
+    .iter()
+    .filter(|parameter| parameter.kind == GenericKind::Type)
+    .map(|parameter| (parameter.name.clone(), parameter.bounds.clone()))
+    .collect::<HashMap<_, _>>();
+
+  for predicate in where_clause {
+    if let Some(parameter) = bounds.get_mut(&predicate.ty) {
+      parameter.extend(predicate.bounds.iter().cloned());
+    }
+  }
+
+  bounds
+}
+
+/// Score a function against the query, returning how loosely it matched
+/// and how many of its parameters the query left unmatched, or `None`.
+fn score_function(
+  signature: &FunctionSignature,
+  mut scope: Scope,
+  inputs: &[(String, TypeTree)],
+  output: Option<&(String, TypeTree)>,
+) -> Option<(usize, usize)> {
+  scope
+    .generics
+    .extend(bounds(&signature.generics, &signature.where_clause));
+
+  let ty = |ty: &str| (normalize(ty), parse_type(ty, &scope));
+
+  let mut parameters = signature
+    .receiver
+    .iter()
+    .map(|receiver| match receiver {
+      Receiver::Value | Receiver::Ref | Receiver::RefMut => ty("Self"),
+      Receiver::Typed(receiver) => ty(receiver),
+    })
+    .chain(
+      signature
+        .parameters
+        .iter()
+        .map(|parameter| ty(&parameter.ty)),
+    )
+    .collect::<Vec<_>>();
+
+  let return_type = ty(signature.return_type.as_deref().unwrap_or("()"));
+
+  match output {
+    Some(output) => {
+      let output = slot_score(output, &return_type)?;
+      let (inputs, unmatched) = assign(inputs, &parameters)?;
+      Some((inputs + output, unmatched))
+    }
+    None => {
+      parameters.push(return_type);
+      assign(inputs, &parameters)
+    }
+  }
+}
+
+/// Match each query type to a different slot, taking the closest one still
+/// free, and return the total score along with the number of free slots left.
+fn assign(
+  queries: &[(String, TypeTree)],
+  slots: &[(String, TypeTree)],
+) -> Option<(usize, usize)> {
+  let mut used = vec![false; slots.len()];
+
+  let mut total = 0;
+
+  for query in queries {
+    let (index, score) = slots
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| !used[*index])
+      .filter_map(|(index, slot)| Some((index, slot_score(query, slot)?)))
+      .min_by_key(|(_, score)| *score)?;
+
+    used[index] = true;
+    total += score;
+  }
+
+  Some((total, used.iter().filter(|used| !**used).count()))
+}
+
+/// Types written identically score 0, and otherwise their trees are compared.
+fn slot_score(
+  (query_text, query): &(String, TypeTree),
+  (slot_text, slot): &(String, TypeTree),
+) -> Option<usize> {
+  if query_text == slot_text {
+    Some(0)
+  } else {
+    score(query, slot)
+  }
+}
+
+/// How loosely `candidate` matches `query`: 1 for the same type, plus the
+/// scores of the generic arguments the query gives, which leaves defaulted
+/// ones like the allocator of `Vec<T, A>` unconstrained, 2 more for each
+/// level the query is nested inside the candidate, 1 more when reached
+/// through a generic parameter's bounds, and 5 for an unbounded generic
+/// parameter. A type variable in the query matches a generic parameter for 1
+/// and any other type for 3.
+fn score(query: &TypeTree, candidate: &TypeTree) -> Option<usize> {
+  if is_unbounded(query) {
+    return Some(match candidate {
+      TypeTree::Generic(_) => 1,
+      TypeTree::Named(..) => 3,
+    });
+  }
+
+  if let TypeTree::Generic(bounds) = query {
+    return bounds
+      .iter()
+      .filter_map(|bound| score(bound, candidate))
+      .min();
+  }
+
+  match candidate {
+    TypeTree::Generic(bounds) => {
+      let bounds = bounds
+        .iter()
+        .filter(|bound| !is_marker(bound))
+        .collect::<Vec<_>>();
+
+      if bounds.is_empty() {
+        return Some(5);
+      }
+
+      bounds
+        .into_iter()
+        .filter_map(|bound| score(query, bound))
+        .min()
+        .map(|score| score + 1)
+    }
+    TypeTree::Named(name, arguments) => {
+      let direct = match query {
+        TypeTree::Named(query_name, query_arguments) if query_name == name => {
+          if query_arguments.is_empty() {
+            Some(1)
+          } else if query_arguments.len() <= arguments.len() {
+            query_arguments
+              .iter()
+              .zip(arguments)
+              .map(|(query, candidate)| score(query, candidate))
+              .sum::<Option<usize>>()
+              .map(|score| score + 1)
+          } else {
+            None
+          }
+        }
+        _ => None,
+      };
+
+      // Anything could be nested in a bare `T`, so only look inside
+      // arguments that say what they are.
+      direct.or_else(|| {
+        arguments
+          .iter()
+          .filter(|argument| !is_unbounded(argument))
+          .filter_map(|argument| score(query, argument))
+          .min()
+          .map(|score| score + 2)
+      })
+    }
+  }
+}
+
+fn is_unbounded(ty: &TypeTree) -> bool {
+  match ty {
+    TypeTree::Generic(bounds) => bounds.iter().all(is_marker),
+    TypeTree::Named(..) => false,
+  }
+}
+
+fn is_marker(bound: &TypeTree) -> bool {
+  match bound {
+    TypeTree::Named(name, _) => {
+      name.starts_with('\'') || MARKER_BOUNDS.contains(&name.as_str())
+    }
+    TypeTree::Generic(_) => false,
+  }
+}
+
+fn parse_type(text: &str, scope: &Scope) -> TypeTree {
+  let mut text = text.trim();
+
+  loop {
+    if let Some(rest) = text.strip_prefix('&') {
+      text = rest.trim_start();
+
+      if text.starts_with('\'') {
+        text = text.split_once(' ').map_or("", |(_, rest)| rest);
+      }
+    } else if let Some(rest) = ["mut ", "*const ", "*mut ", "dyn "]
+      .iter()
+      .find_map(|prefix| text.strip_prefix(prefix))
+    {
+      text = rest.trim_start();
+    } else if text.starts_with("for<") {
+      match closing(&text[3..]) {
+        Some(end) => text = text[end + 4..].trim_start(),
+        None => break,
+      }
+    } else {
+      break;
+    }
+  }
+
+  if let Some(bounds) = text.strip_prefix("impl ") {
+    return TypeTree::Generic(
+      split_top_level(bounds, '+')
+        .into_iter()
+        .map(|bound| parse_type(bound, scope))
+        .collect(),
+    );
+  }
+
+  // Of `Error + Send + Sync`, only the first bound is the type.
+  let text = split_top_level(text, '+')
+    .into_iter()
+    .next()
+    .unwrap_or_default();
+
+  if text.starts_with('[') || text.starts_with('(') {
+    let Some(end) = closing(text) else {
+      return TypeTree::Named(text.to_string(), Vec::new());
+    };
+
+    let inner = &text[1..end];
+
+    if text.starts_with('[') {
+      let element = split_top_level(inner, ';')
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+
+      return TypeTree::Named("[]".into(), vec![parse_type(element, scope)]);
+    }
+
+    let elements = split_top_level(inner, ',');
+
+    if elements.len() == 1 && !inner.trim_end().ends_with(',') {
+      return parse_type(elements[0], scope);
+    }
+
+    return TypeTree::Named(
+      "()".into(),
+      elements
+        .into_iter()
+        .map(|element| parse_type(element, scope))
+        .collect(),
+    );
+  }
+
+  // A qualified path such as `<T as Iterator>::Item`.
+  if text.starts_with('<') {
+    return TypeTree::Generic(Vec::new());
+  }
+
+  let (path, arguments) = match text
+    .find(['<', '('])
+    .and_then(|start| Some((start, start + closing(&text[start..])?)))
+  {
+    Some((start, end)) => {
+      let mut arguments = split_top_level(&text[start + 1..end], ',')
+        .into_iter()
+        .filter(|argument| !argument.starts_with('\''))
+        .map(|argument| parse_type(binding(argument), scope))
+        .collect::<Vec<_>>();
+
+      // The return type of `Fn(A) -> B` counts as one of its arguments.
+      if let Some(output) = text[end + 1..].trim_start().strip_prefix("->") {
+        arguments.push(parse_type(output, scope));
+      }
+
+      (&text[..start], arguments)
+    }
+    None => (text, Vec::new()),
+  };
+
+  let segments = path.split("::").map(str::trim).collect::<Vec<_>>();
+
+  let first = segments[0];
+
+  if segments.len() > 1
+    && (first == "Self" || scope.generics.contains_key(first))
+  {
+    return TypeTree::Generic(Vec::new());
+  }
+
+  if segments.len() == 1 {
+    if let (Some(self_type), "Self") = (&scope.self_type, first) {
+      return self_type.clone();
+    }
+
+    if let Some(bounds) = scope.generics.get(first) {
+      return TypeTree::Generic(
+        bounds
+          .iter()
+          .map(|bound| parse_type(bound, &Scope::default()))
+          .collect(),
+      );
+    }
+  }
+
+  TypeTree::Named(
+    segments.last().copied().unwrap_or_default().to_string(),
+    arguments,
+  )
+}
+
+/// The type bound in an associated type binding like `Item = Bytes`, or the
+/// argument as is.
+fn binding(argument: &str) -> &str {
+  match argument.split_once('=') {
+    Some((name, ty))
+      if name.trim().chars().all(|c| c.is_alphanumeric() || c == '_') =>
+    {
+      ty.trim()
+    }
+    _ => argument,
+  }
+}
+
+/// Split a query at its top-level `->`, if it has one.
+fn split_arrow(query: &str) -> (&str, Option<&str>) {
+  let mut depth = 0usize;
+
+  let mut previous = ' ';
+
+  for (index, c) in query.char_indices() {
+    match c {
+      '<' | '(' | '[' => depth += 1,
+      '>' if previous == '-' && depth == 0 => {
+        return (&query[..index - 1], Some(&query[index + 1..]));
+      }
+      '>' if previous == '-' => {}
+      '>' | ')' | ']' => depth = depth.saturating_sub(1),
+      _ => {}
+    }
+
+    previous = c;
+  }
+
+  (query, None)
+}
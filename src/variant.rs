@@ -0,0 +1,16 @@
+use super::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Variant {
+  pub name: String,
+  pub kind: VariantKind,
+  pub discriminant: Option<String>,
+  pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum VariantKind {
+  Unit,
+  Tuple(Vec<String>),
+  Struct(Vec<Field>),
+}
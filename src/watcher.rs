@@ -0,0 +1,158 @@
+use {
+  super::*,
+  notify::{Event, RecommendedWatcher, RecursiveMode, Watcher},
+  std::time::Duration,
+  tokio::{sync::mpsc, time},
+};
+
+/// How long the documentation directory has to stay quiet before a burst of
+/// writes from `cargo doc` is acted on.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watch the documentation directory, and the per-target trees beside it,
+/// for as long as the returned watcher is alive. Once a `cargo doc` run
+/// settles, the crates whose pages changed are re-indexed into the cache, and
+/// the client is sent a resource list change notification and a log message
+/// naming them. Nothing is created, so a directory `cargo doc` hasn't written
+/// yet, or a target first documented after startup, isn't watched.
+pub fn watch(
+  options: Options,
+  peer: Peer<RoleServer>,
+  log_level: Arc<Mutex<LoggingLevel>>,
+) -> Result<RecommendedWatcher> {
+  let path = options.doc_path().to_string();
+
+  if !Path::new(&path).is_dir() {
+    return Err(Error(anyhow!(
+      "it does not exist yet, run `cargo doc` and restart the server to pick \
+       up changes"
+    )));
+  }
+
+  let roots = iter::once((None, PathBuf::from(&path)))
+    .chain(
+      list_targets(Path::new(&path))
+        .into_iter()
+        .map(|(target, root)| (Some(target), root)),
+    )
+    .map(|(target, root)| Ok((target, fs::canonicalize(root)?)))
+    .collect::<Result<Vec<_>>>()?;
+
+  let (sender, mut receiver) = mpsc::unbounded_channel();
+
+  let mut watcher = notify::recommended_watcher(
+    move |event: notify::Result<Event>| match event {
+      Ok(event) if !event.kind.is_access() && !event.kind.is_other() => {
+        sender.send(event.paths).ok();
+      }
+      Ok(_) => {}
+      Err(error) => warn!("documentation watcher error: {error}"),
+    },
+  )?;
+
+  for (_, root) in &roots {
+    watcher.watch(root, RecursiveMode::Recursive)?;
+  }
+
+  task::spawn(async move {
+    while let Some(mut paths) = receiver.recv().await {
+      while let Ok(Some(more)) = time::timeout(DEBOUNCE, receiver.recv()).await
+      {
+        paths.extend(more);
+      }
+
+      let crates = roots
+        .iter()
+        .flat_map(|(target, root)| {
+          changed_crates(root, &paths)
+            .into_iter()
+            .map(|name| (name, target.clone()))
+        })
+        .collect::<BTreeSet<_>>();
+
+      if crates.is_empty() {
+        continue;
+      }
+
+      let failures = if options.no_cache {
+        Vec::new()
+      } else {
+        let (path, cache, crates) =
+          (path.clone(), options.cache(), crates.clone());
+
+        task::spawn_blocking(move || reindex(&path, &cache, crates))
+          .await
+          .unwrap_or_default()
+      };
+
+      let crates = crates
+        .into_iter()
+        .map(|(name, target)| match target {
+          Some(target) => format!("{name} ({target})"),
+          None => name,
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+      info!("documentation changed for {crates}");
+
+      if let Err(error) = peer.notify_resource_list_changed().await {
+        warn!("failed to notify client of changed resources: {error}");
+      }
+
+      let messages = [(
+        LoggingLevel::Info,
+        format!("Documentation changed for {crates}"),
+      )]
+      .into_iter()
+      .chain(
+        failures
+          .into_iter()
+          .map(|failure| (LoggingLevel::Warning, failure)),
+      );
+
+      for (level, message) in messages {
+        if (level as u8) < (*log_level.lock().unwrap() as u8) {
+          continue;
+        }
+
+        let notification = LoggingMessageNotificationParam {
+          level,
+          logger: Some(env!("CARGO_PKG_NAME").into()),
+          data: message.into(),
+        };
+
+        if let Err(error) = peer.notify_logging_message(notification).await {
+          warn!("failed to send log message to client: {error}");
+        }
+      }
+    }
+  });
+
+  Ok(watcher)
+}
+
+/// Parse every page of the given crates into the cache, returning a message
+/// for each crate that could not be indexed, such as one `cargo clean` just
+/// removed.
+fn reindex(
+  path: &str,
+  cache: &Cache,
+  crates: BTreeSet<(String, Option<String>)>,
+) -> Vec<String> {
+  crates
+    .into_iter()
+    .filter_map(|(name, target)| {
+      let request = LookupCrateRequest {
+        name,
+        target,
+        ..Default::default()
+      };
+
+      lookup_crate(&request, path, cache).err().map(|error| {
+        warn!("failed to re-index '{}': {error}", request.name);
+        format!("Failed to re-index {}: {error}", request.name)
+      })
+    })
+    .collect()
+}
@@ -0,0 +1,9 @@
+use super::*;
+
+/// One predicate of a `where` clause, such as `T: Clone + Send`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WherePredicate {
+  #[serde(rename = "type")]
+  pub ty: String,
+  pub bounds: Vec<String>,
+}
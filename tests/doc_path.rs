@@ -0,0 +1,79 @@
+//! How the documentation directory is chosen, checked by running the binary
+//! with its own environment rather than mutating this process's.
+
+use {
+  std::{fs, path::Path, process::Command},
+  tempfile::TempDir,
+};
+
+fn documented(root: &Path, crate_name: &str) -> String {
+  let doc_path = root.join("doc");
+
+  fs::create_dir_all(doc_path.join(crate_name)).unwrap();
+
+  doc_path.to_string_lossy().into_owned()
+}
+
+fn list(directory: &Path, env: Option<&str>, flag: Option<&str>) -> String {
+  let mut command = Command::new(env!("CARGO_BIN_EXE_crates-mcp"));
+
+  command
+    .current_dir(directory)
+    .env_remove("CARGO_TARGET_DIR")
+    .env_remove("CRATES_MCP_DOC_PATH");
+
+  if let Some(env) = env {
+    command.env("CRATES_MCP_DOC_PATH", env);
+  }
+
+  if let Some(flag) = flag {
+    command.args(["--doc-path", flag]);
+  }
+
+  let output = command.arg("list").output().unwrap();
+
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn doc_path_precedence() {
+  let temp_dir = TempDir::new().unwrap();
+
+  let package = temp_dir.path().join("package");
+
+  fs::create_dir_all(package.join("src")).unwrap();
+
+  fs::write(
+    package.join("Cargo.toml"),
+    "[package]\nname = \"probe\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+  )
+  .unwrap();
+
+  fs::write(package.join("src/lib.rs"), "").unwrap();
+
+  documented(&package.join("target"), "from_metadata");
+
+  let env = documented(&temp_dir.path().join("env"), "from_env");
+
+  let flag = documented(&temp_dir.path().join("flag"), "from_flag");
+
+  let crates = |env, flag| {
+    list(&package, env, flag)
+      .lines()
+      .filter(|name| name.starts_with("from_"))
+      .map(str::to_string)
+      .collect::<Vec<_>>()
+  };
+
+  assert_eq!(crates(None, None), vec!["from_metadata"]);
+
+  assert_eq!(crates(Some(&env), None), vec!["from_env"]);
+
+  assert_eq!(crates(Some(&env), Some(&flag)), vec!["from_flag"]);
+}